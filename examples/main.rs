@@ -2,8 +2,8 @@ use std::{collections::BTreeMap, sync::Arc};
 
 use anyhow::Context;
 use pandascore::Client;
-use trustfall::{execute_query, FieldValue};
-use trustfall_pandascore_adapter::Adapter;
+use trustfall::FieldValue;
+use trustfall_pandascore_adapter::{Adapter, ErrorPolicy};
 
 const QUERY: &str = include_str!("query.graphql");
 
@@ -18,40 +18,19 @@ fn main() -> anyhow::Result<()> {
     let args = std::env::args().skip(2).collect::<Vec<String>>();
 
     let client = Client::new(reqwest::Client::new(), token)?;
-    let adapter = Arc::new(Adapter::new(client));
-
-    let res = execute_query(
-        Adapter::<reqwest::Client>::schema(),
-        Arc::clone(&adapter),
-        QUERY,
-        parse_args(args),
-    )?;
-
-    if !adapter.errors().is_empty() {
-        for error in adapter.errors().iter() {
-            eprintln!("{}", error);
-        }
-    }
-
-    for (index, data_item) in res.enumerate() {
-        if !adapter.errors().is_empty() {
-            for error in adapter.errors().iter() {
-                eprintln!("{}", error);
-            }
-            break;
-        }
-
-        println!("{:#?}", data_item);
-
-        if index + 1 >= max_results {
-            break;
-        }
+    let adapter = Arc::new(
+        Adapter::builder(client)
+            .error_policy(ErrorPolicy::FailFast)
+            .build(),
+    );
+
+    let res = adapter.execute_query(QUERY, parse_args(args))?;
+    for data_item in res.take(max_results) {
+        println!("{:#?}", data_item?);
     }
 
-    if !adapter.errors().is_empty() {
-        for error in adapter.errors().iter() {
-            eprintln!("{}", error);
-        }
+    for error in adapter.errors().iter() {
+        eprintln!("{}", error);
     }
 
     Ok(())
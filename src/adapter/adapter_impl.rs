@@ -3,197 +3,259 @@ use std::sync::Arc;
 use pandascore::ClientTransport;
 use trustfall::{
     provider::{
-        resolve_coercion_using_schema, AsVertex, ContextIterator, ContextOutcomeIterator,
-        EdgeParameters, VertexIterator,
+        resolve_coercion_using_schema, resolve_property_with, AsVertex, ContextIterator,
+        ContextOutcomeIterator, EdgeParameters, ResolveEdgeInfo, ResolveInfo, Typename,
+        VertexIterator,
     },
     FieldValue,
 };
 
-use crate::adapter::{Adapter, Vertex};
+use crate::adapter::{
+    params::{EntrypointParams, Progress, TimeField},
+    Adapter, AdapterError, ErrorContext, Vertex,
+};
 
-impl<'a, T: ClientTransport + 'a> trustfall::provider::BasicAdapter<'a> for Adapter<T> {
+impl<'a, T: ClientTransport + 'a> trustfall::provider::Adapter<'a> for Adapter<T> {
     type Vertex = Vertex;
 
     fn resolve_starting_vertices(
         &self,
-        edge_name: &str,
+        edge_name: &Arc<str>,
         parameters: &EdgeParameters,
+        resolve_info: &ResolveInfo,
     ) -> VertexIterator<'a, Self::Vertex> {
-        match edge_name {
-            "Leagues" => {
-                let game = parameters
-                    .get("game")
-                    .expect("failed to find parameter 'game' when resolving 'Leagues' starting vertices")
-                    .as_str();
-                let search = parameters
-                    .get("search")
-                    .expect("failed to find parameter 'search' when resolving 'Leagues' starting vertices")
-                    .as_str();
-                super::entrypoints::leagues(Arc::clone(&self.0), game, search)
+        let time = match edge_name.as_ref() {
+            "Leagues" | "Teams" | "Players" => TimeField::ModifiedAt,
+            _ => TimeField::BeginAt,
+        };
+        let Some(params) = EntrypointParams::parse(&self.0, parameters, time) else {
+            return Box::new(std::iter::empty());
+        };
+        let adapter = Arc::clone(&self.0);
+        let context = ErrorContext::at(edge_name.as_ref());
+        match edge_name.as_ref() {
+            "Leagues" => super::entrypoints::leagues(adapter, context, &params, resolve_info),
+            "Series" => super::entrypoints::series(adapter, context, &params, resolve_info),
+            "Tournaments" => {
+                super::entrypoints::tournaments(adapter, context, &params, resolve_info)
             }
-            "Series" => {
-                let game = parameters
-                    .get("game")
-                    .expect(
-                        "failed to find parameter 'game' when resolving 'Series' starting vertices",
-                    )
-                    .as_str();
-                let search = parameters
-                    .get("search")
-                    .expect("failed to find parameter 'search' when resolving 'Series' starting vertices")
-                    .as_str();
-                super::entrypoints::series(Arc::clone(&self.0), game, search)
+            "RunningSeries" => {
+                let params = params.with_progress(Progress::Running);
+                super::entrypoints::series(adapter, context, &params, resolve_info)
+            }
+            "UpcomingSeries" => {
+                let params = params.with_progress(Progress::Upcoming);
+                super::entrypoints::series(adapter, context, &params, resolve_info)
+            }
+            "PastSeries" => {
+                let params = params.with_progress(Progress::Past);
+                super::entrypoints::series(adapter, context, &params, resolve_info)
+            }
+            "RunningTournaments" => {
+                let params = params.with_progress(Progress::Running);
+                super::entrypoints::tournaments(adapter, context, &params, resolve_info)
+            }
+            "UpcomingTournaments" => {
+                let params = params.with_progress(Progress::Upcoming);
+                super::entrypoints::tournaments(adapter, context, &params, resolve_info)
+            }
+            "PastTournaments" => {
+                let params = params.with_progress(Progress::Past);
+                super::entrypoints::tournaments(adapter, context, &params, resolve_info)
+            }
+            "Matches" => super::entrypoints::matches(adapter, context, &params, resolve_info),
+            "RunningMatches" => {
+                let params = params.with_status("running");
+                super::entrypoints::matches(adapter, context, &params, resolve_info)
+            }
+            "UpcomingMatches" => {
+                let params = params.with_status("not_started");
+                super::entrypoints::matches(adapter, context, &params, resolve_info)
+            }
+            "PastMatches" => {
+                let params = params.with_status("finished");
+                super::entrypoints::matches(adapter, context, &params, resolve_info)
+            }
+            "Teams" => super::entrypoints::teams(adapter, context, &params, resolve_info),
+            "Players" => super::entrypoints::players(adapter, context, &params, resolve_info),
+            "VideoGames" => super::entrypoints::video_games(adapter),
+            _ => {
+                self.0.report(AdapterError::UnknownEdge {
+                    type_name: "RootSchemaQuery".to_owned(),
+                    edge: edge_name.to_string(),
+                });
+                Box::new(std::iter::empty())
             }
-            "Tournaments" => {
-                let game = parameters
-                    .get("game")
-                    .expect("failed to find parameter 'game' when resolving 'Tournaments' starting vertices")
-                    .as_str();
-                let search = parameters
-                    .get("search")
-                    .expect("failed to find parameter 'search' when resolving 'Tournaments' starting vertices")
-                    .as_str();
-                super::entrypoints::tournaments(Arc::clone(&self.0), game, search)
-            }
-            "Matches" => {
-                let game = parameters
-                    .get("game")
-                    .expect("failed to find parameter 'game' when resolving 'Matches' starting vertices")
-                    .as_str();
-                let search = parameters
-                    .get("search")
-                    .expect("failed to find parameter 'search' when resolving 'Matches' starting vertices")
-                    .as_str();
-                super::entrypoints::matches(Arc::clone(&self.0), game, search)
-            }
-            "Teams" => {
-                let game = parameters
-                    .get("game")
-                    .expect(
-                        "failed to find parameter 'game' when resolving 'Teams' starting vertices",
-                    )
-                    .as_str();
-                let search = parameters
-                    .get("search")
-                    .expect("failed to find parameter 'search' when resolving 'Teams' starting vertices")
-                    .as_str();
-                super::entrypoints::teams(Arc::clone(&self.0), game, search)
-            }
-            "Players" => {
-                let game = parameters
-                    .get("game")
-                    .expect("failed to find parameter 'game' when resolving 'Players' starting vertices")
-                    .as_str();
-                let search = parameters
-                    .get("search")
-                    .expect("failed to find parameter 'search' when resolving 'Players' starting vertices")
-                    .as_str();
-                super::entrypoints::players(Arc::clone(&self.0), game, search)
-            }
-            _ => unreachable!(
-                "attempted to resolve starting vertices for unexpected edge name: {edge_name}"
-            ),
         }
     }
 
     fn resolve_property<V: AsVertex<Self::Vertex> + 'a>(
         &self,
         contexts: ContextIterator<'a, V>,
-        type_name: &str,
-        property_name: &str,
+        type_name: &Arc<str>,
+        property_name: &Arc<str>,
+        _resolve_info: &ResolveInfo,
     ) -> ContextOutcomeIterator<'a, V, FieldValue> {
-        match type_name {
-            "League" => super::properties::resolve_league_property(contexts, property_name),
-            "Series" => super::properties::resolve_series_property(contexts, property_name),
-            "Tournament" => super::properties::resolve_tournament_property(contexts, property_name),
-            "Match" => super::properties::resolve_match_property(contexts, property_name),
-            "Team" => super::properties::resolve_team_property(contexts, property_name),
-            "Player" => super::properties::resolve_player_property(contexts, property_name),
-            "VideoGame" => super::properties::resolve_video_game_property(contexts, property_name),
-            "Winner" => super::properties::resolve_winner_property(contexts, property_name),
+        if property_name.as_ref() == "__typename" {
+            return resolve_property_with(contexts, |vertex| vertex.typename().into());
+        }
+
+        let adapter = Arc::clone(&self.0);
+        match type_name.as_ref() {
+            "League" => {
+                super::properties::resolve_league_property(adapter, contexts, property_name)
+            }
+            "Series" => {
+                super::properties::resolve_series_property(adapter, contexts, property_name)
+            }
+            "Tournament" => {
+                super::properties::resolve_tournament_property(adapter, contexts, property_name)
+            }
+            "Match" => super::properties::resolve_match_property(adapter, contexts, property_name),
+            "MatchResult" => {
+                super::properties::resolve_match_result_property(adapter, contexts, property_name)
+            }
+            "Team" => super::properties::resolve_team_property(adapter, contexts, property_name),
+            "Player" => {
+                super::properties::resolve_player_property(adapter, contexts, property_name)
+            }
+            "VideoGame" => {
+                super::properties::resolve_video_game_property(adapter, contexts, property_name)
+            }
+            "Winner" => {
+                super::properties::resolve_winner_property(adapter, contexts, property_name)
+            }
             "WinnerTeam" => {
-                super::properties::resolve_winner_team_property(contexts, property_name)
+                super::properties::resolve_winner_team_property(adapter, contexts, property_name)
             }
             "WinnerPlayer" => {
-                super::properties::resolve_winner_player_property(contexts, property_name)
+                super::properties::resolve_winner_player_property(adapter, contexts, property_name)
             }
-            _ => {
-                unreachable!(
-                    "attempted to read property '{property_name}' on unexpected type: {type_name}"
-                )
+            "Opponent" => {
+                super::properties::resolve_opponent_property(adapter, contexts, property_name)
+            }
+            "OpponentTeam" => {
+                super::properties::resolve_opponent_team_property(adapter, contexts, property_name)
             }
+            "OpponentPlayer" => super::properties::resolve_opponent_player_property(
+                adapter,
+                contexts,
+                property_name,
+            ),
+            _ => super::properties::unknown_property(&adapter, contexts, type_name, property_name),
         }
     }
 
     fn resolve_neighbors<V: AsVertex<Self::Vertex> + 'a>(
         &self,
         contexts: ContextIterator<'a, V>,
-        type_name: &str,
-        edge_name: &str,
+        type_name: &Arc<str>,
+        edge_name: &Arc<str>,
         parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Self::Vertex>> {
-        match type_name {
+        match type_name.as_ref() {
             "League" => super::edges::resolve_league_edge(
                 Arc::clone(&self.0),
                 contexts,
                 edge_name,
                 parameters,
+                resolve_info,
             ),
             "Series" => super::edges::resolve_series_edge(
                 Arc::clone(&self.0),
                 contexts,
                 edge_name,
                 parameters,
+                resolve_info,
             ),
             "Tournament" => super::edges::resolve_tournament_edge(
                 Arc::clone(&self.0),
                 contexts,
                 edge_name,
                 parameters,
+                resolve_info,
             ),
             "Team" => super::edges::resolve_team_edge(
                 Arc::clone(&self.0),
                 contexts,
                 edge_name,
                 parameters,
+                resolve_info,
             ),
             "Player" => super::edges::resolve_player_edge(
                 Arc::clone(&self.0),
                 contexts,
                 edge_name,
                 parameters,
+                resolve_info,
             ),
             "Match" => super::edges::resolve_match_edge(
                 Arc::clone(&self.0),
                 contexts,
                 edge_name,
                 parameters,
+                resolve_info,
             ),
             "WinnerTeam" => super::edges::resolve_winner_team_edge(
                 Arc::clone(&self.0),
                 contexts,
                 edge_name,
                 parameters,
+                resolve_info,
             ),
             "WinnerPlayer" => super::edges::resolve_winner_player_edge(
                 Arc::clone(&self.0),
                 contexts,
                 edge_name,
                 parameters,
+                resolve_info,
             ),
-            _ => {
-                unreachable!(
-                    "attempted to resolve edge '{edge_name}' on unexpected type: {type_name}"
-                )
-            }
+            "VideoGame" => super::edges::resolve_video_game_edge(
+                Arc::clone(&self.0),
+                contexts,
+                edge_name,
+                parameters,
+                resolve_info,
+            ),
+            "MatchResult" => super::edges::resolve_match_result_edge(
+                Arc::clone(&self.0),
+                contexts,
+                edge_name,
+                parameters,
+                resolve_info,
+            ),
+            "Roster" => super::edges::resolve_roster_edge(
+                Arc::clone(&self.0),
+                contexts,
+                edge_name,
+                parameters,
+                resolve_info,
+            ),
+            "OpponentTeam" => super::edges::resolve_opponent_team_edge(
+                Arc::clone(&self.0),
+                contexts,
+                edge_name,
+                parameters,
+                resolve_info,
+            ),
+            "OpponentPlayer" => super::edges::resolve_opponent_player_edge(
+                Arc::clone(&self.0),
+                contexts,
+                edge_name,
+                parameters,
+                resolve_info,
+            ),
+            _ => super::edges::unknown_edge(&self.0, contexts, type_name, edge_name),
         }
     }
 
     fn resolve_coercion<V: AsVertex<Self::Vertex> + 'a>(
         &self,
         contexts: ContextIterator<'a, V>,
-        _type_name: &str,
-        coerce_to_type: &str,
+        _type_name: &Arc<str>,
+        coerce_to_type: &Arc<str>,
+        _resolve_info: &ResolveInfo,
     ) -> ContextOutcomeIterator<'a, V, bool> {
         resolve_coercion_using_schema(contexts, Self::schema(), coerce_to_type)
     }
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use pandascore::{Client, ClientTransport};
+use tokio::runtime::Handle;
+
+use crate::{
+    adapter::{
+        cache::VertexCache, Adapter, AdapterInner, CacheConfig, ErrorPolicy, Executor, RetryPolicy,
+        Shared,
+    },
+    rate_limit::RateLimiter,
+};
+
+/// Builds an [`Adapter`] with non-default settings.
+#[derive(Debug)]
+pub struct AdapterBuilder<T> {
+    client: Client<T>,
+    cache: CacheConfig,
+    read_ahead: usize,
+    handle: Option<Handle>,
+    rate_limiter: Option<RateLimiter>,
+    retry: RetryPolicy,
+    error_policy: ErrorPolicy,
+}
+
+impl<T: ClientTransport> AdapterBuilder<T> {
+    pub(super) fn new(client: Client<T>) -> Self {
+        Self {
+            client,
+            cache: CacheConfig::disabled(),
+            read_ahead: 0,
+            handle: None,
+            rate_limiter: None,
+            retry: RetryPolicy::default(),
+            error_policy: ErrorPolicy::default(),
+        }
+    }
+
+    /// Sets the size and time to live of the in-memory entity cache.
+    ///
+    /// Defaults to [`CacheConfig::disabled`], so that every query sees fresh data.
+    /// [`CacheConfig::default`] keeps up to 10,000 entities for 10 minutes.
+    #[must_use]
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Sets how many pages of a paginated endpoint are fetched concurrently with the next one.
+    ///
+    /// The next page and up to `pages` pages after it are fetched concurrently in one batch, which
+    /// is started as soon as the previous pages are handed out, so that it is in flight while
+    /// their rows are consumed. At most one batch is fetched ahead of the rows being consumed.
+    /// On an existing runtime given with [`handle`](Self::handle), the batch only progresses in
+    /// the background if the runtime has worker threads.
+    ///
+    /// Read-ahead speeds up scans of whole collections, but can fetch pages a query never reads.
+    /// Defaults to `0`, which fetches one page at a time.
+    #[must_use]
+    pub fn read_ahead(mut self, pages: usize) -> Self {
+        self.read_ahead = pages;
+        self
+    }
+
+    /// Executes requests on the runtime behind `handle`, instead of a runtime owned by the adapter.
+    ///
+    /// This lets queries run inside async code, where blocking on a runtime of the adapter's own
+    /// would panic. When a query runs on a worker thread of that runtime, the thread is handed
+    /// over with [`tokio::task::block_in_place`], so the runtime must be a multi-threaded one.
+    /// Requests made from inside a current-thread runtime fail with
+    /// [`AdapterError::CurrentThreadRuntime`](crate::AdapterError::CurrentThreadRuntime).
+    #[must_use]
+    pub fn handle(mut self, handle: Handle) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+
+    /// Delays requests to stay within the limits of `limiter`, instead of failing once the API
+    /// quota is used up.
+    ///
+    /// An existing runtime given with [`handle`](Self::handle) must have its timer enabled.
+    #[must_use]
+    pub fn rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Retries failed requests as `policy` says, instead of recording their errors right away.
+    ///
+    /// For retries to wait as long as the `Retry-After` headers of failed responses ask, the
+    /// client must be created with a transport wrapped by [`RetryPolicy::track_headers`] of
+    /// `policy` or a clone of it. A warning is logged when it isn't.
+    ///
+    /// Defaults to [`RetryPolicy::default`], which never retries.
+    #[must_use]
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        #[cfg(feature = "log")]
+        {
+            if policy.ignores_retry_after() {
+                log::warn!(
+                    "Retry-After headers are ignored, since the client's transport isn't wrapped \
+                     with RetryPolicy::track_headers"
+                );
+            }
+        }
+        self.retry = policy;
+        self
+    }
+
+    /// Sets what the adapter does with errors. Defaults to [`ErrorPolicy::Collect`].
+    #[must_use]
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = policy;
+        self
+    }
+
+    pub fn build(self) -> Adapter<T> {
+        let runtime = match self.handle {
+            Some(handle) => Executor::Handle(handle),
+            None => Executor::Owned(Some(
+                // A worker thread lets read-ahead requests progress while rows are consumed.
+                tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(1)
+                    .enable_all()
+                    .build()
+                    .unwrap(),
+            )),
+        };
+
+        Adapter(Arc::new(AdapterInner {
+            shared: Arc::new(Shared {
+                runtime,
+                client: self.client,
+                cache: VertexCache::new(self.cache),
+                read_ahead: self.read_ahead,
+                rate_limiter: self.rate_limiter,
+                retry: self.retry,
+                error_policy: self.error_policy,
+            }),
+            query: Arc::default(),
+        }))
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use pandascore::{
+    endpoint::{all, CollectionOptions, Endpoint, ListResponse},
+    model::{
+        league::League, matches::Match, player::Player, series::Series, team::Team,
+        tournament::Tournament,
+    },
+};
+
+use crate::adapter::Vertex;
+
+/// Settings for the adapter's in-memory cache of leagues, series, tournaments, matches, teams and
+/// players.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CacheConfig {
+    /// The most entities to keep, evicting the oldest first. `0` disables the cache.
+    pub capacity: usize,
+    /// How long a cached entity is used before it is fetched again.
+    pub ttl: Duration,
+}
+
+impl CacheConfig {
+    pub const fn new(capacity: usize, ttl: Duration) -> Self {
+        Self { capacity, ttl }
+    }
+
+    /// A configuration that caches nothing.
+    pub const fn disabled() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+}
+
+/// Keeps up to 10,000 entities for 10 minutes.
+///
+/// The adapter only caches when given a configuration with [`AdapterBuilder::cache`](crate::AdapterBuilder::cache).
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self::new(10_000, Duration::from_secs(10 * 60))
+    }
+}
+
+/// The kind of entity a cache entry holds, since ids are only unique per kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum EntityKind {
+    League,
+    Series,
+    Tournament,
+    Match,
+    Team,
+    Player,
+}
+
+/// An entity that can be fetched by id and kept in the [`VertexCache`].
+pub(crate) trait Cached: Sized {
+    const KIND: EntityKind;
+
+    /// The endpoint fetching a single entity by id.
+    type Get: Endpoint<Response = Self> + From<u64> + Clone + Debug;
+    /// The endpoint listing entities across every game.
+    type List: Endpoint<Response = ListResponse<Self>> + Clone + Debug;
+
+    fn list(options: CollectionOptions) -> Self::List;
+    fn id(&self) -> u64;
+    fn to_vertex(&self) -> Vertex;
+    fn from_vertex(vertex: Vertex) -> Option<Self>;
+}
+
+macro_rules! impl_cached {
+    (
+        $ty:ty, $kind:ident, $get:ty, $list:ty, $make_list:expr, $to:expr,
+        $from:pat => $entity:expr
+    ) => {
+        impl Cached for $ty {
+            const KIND: EntityKind = EntityKind::$kind;
+            type Get = $get;
+            type List = $list;
+
+            fn list(options: CollectionOptions) -> Self::List {
+                $make_list(options)
+            }
+
+            fn id(&self) -> u64 {
+                self.id
+            }
+
+            fn to_vertex(&self) -> Vertex {
+                $to(self.clone())
+            }
+
+            fn from_vertex(vertex: Vertex) -> Option<Self> {
+                match vertex {
+                    $from => Some($entity),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_cached!(
+    League,
+    League,
+    all::leagues::GetLeague<'static>,
+    all::leagues::ListLeagues,
+    all::leagues::ListLeagues,
+    Vertex::League,
+    Vertex::League(x) => x
+);
+impl_cached!(
+    Series,
+    Series,
+    all::series::GetSeries<'static>,
+    all::series::ListSeries,
+    |options| all::series::ListSeries::builder().options(options).build(),
+    Vertex::Series,
+    Vertex::Series(x) => x
+);
+impl_cached!(
+    Tournament,
+    Tournament,
+    all::tournament::GetTournament<'static>,
+    all::tournament::ListTournaments,
+    |options| all::tournament::ListTournaments::builder().options(options).build(),
+    |x| Vertex::Tournament(Box::new(x)),
+    Vertex::Tournament(x) => *x
+);
+impl_cached!(
+    Match,
+    Match,
+    all::matches::GetMatch<'static>,
+    all::matches::ListMatches,
+    |options| all::matches::ListMatches::builder().options(options).build(),
+    |x| Vertex::Match(Box::new(x)),
+    Vertex::Match(x) => *x
+);
+impl_cached!(
+    Team,
+    Team,
+    all::teams::GetTeam<'static>,
+    all::teams::ListTeams,
+    all::teams::ListTeams,
+    Vertex::Team,
+    Vertex::Team(x) => x
+);
+impl_cached!(
+    Player,
+    Player,
+    all::players::GetPlayer<'static>,
+    all::players::ListPlayers,
+    all::players::ListPlayers,
+    Vertex::Player,
+    Vertex::Player(x) => x
+);
+
+type Key = (EntityKind, u64);
+
+/// A size-bounded cache of entities with a time to live, keyed by kind and id.
+#[derive(Debug)]
+pub(crate) struct VertexCache {
+    config: CacheConfig,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<Key, (Vertex, Instant)>,
+    /// Keys in insertion order. Keys that were replaced or evicted since are skipped lazily.
+    order: VecDeque<(Key, Instant)>,
+}
+
+impl VertexCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Returns the cached entity with `id`, if it is still fresh.
+    pub(crate) fn get<E: Cached>(&self, id: u64) -> Option<E> {
+        if self.config.capacity == 0 {
+            return None;
+        }
+
+        let key = (E::KIND, id);
+        let mut state = self.state.lock().unwrap();
+        let (vertex, inserted) = state.entries.get(&key)?;
+        if inserted.elapsed() < self.config.ttl {
+            return E::from_vertex(vertex.clone());
+        }
+        state.entries.remove(&key);
+        None
+    }
+
+    /// Caches `entity`, evicting the oldest entries if the cache is full.
+    pub(crate) fn insert<E: Cached>(&self, entity: &E) {
+        if self.config.capacity == 0 {
+            return;
+        }
+
+        let key = (E::KIND, entity.id());
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        state.entries.insert(key, (entity.to_vertex(), now));
+        state.order.push_back((key, now));
+
+        while state.entries.len() > self.config.capacity {
+            let Some((key, inserted)) = state.order.pop_front() else {
+                break;
+            };
+            if state
+                .entries
+                .get(&key)
+                .is_some_and(|(_, at)| *at == inserted)
+            {
+                state.entries.remove(&key);
+            }
+        }
+
+        // Re-inserted keys leave stale entries behind, so drop them before they pile up.
+        if state.order.len() > 2 * self.config.capacity {
+            let CacheState { entries, order } = &mut *state;
+            order.retain(|(key, inserted)| entries.get(key).is_some_and(|(_, at)| at == inserted));
+        }
+    }
+}
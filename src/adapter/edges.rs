@@ -1,24 +1,29 @@
 use std::sync::Arc;
 
-use pandascore::{endpoint::all, model::Winner, ClientTransport};
+use pandascore::{endpoint::CollectionOptions, model::Winner, ClientTransport};
 use trustfall::provider::{
-    AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters, VertexIterator,
+    AsVertex, ContextIterator, ContextOutcomeIterator, DataContext, EdgeParameters,
+    ResolveEdgeInfo, VertexIterator,
 };
 
-use super::vertex::Vertex;
-use crate::adapter::AdapterInner;
+use super::{
+    cache::Cached,
+    hints,
+    params::{CollectionParams, TimeField, MAX_PAGE_SIZE},
+    vertex::Vertex,
+};
+use crate::adapter::{AdapterError, AdapterInner, ErrorContext};
 
 pub(super) fn resolve_league_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
-    _parameters: &EdgeParameters,
+    parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "series" => league::series(adapter, contexts),
-        _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'League'")
-        }
+        "series" => league::series(adapter, contexts, parameters, resolve_info),
+        _ => unknown_edge(&adapter, contexts, "League", edge_name),
     }
 }
 
@@ -27,30 +32,53 @@ mod league {
 
     use pandascore::{endpoint::all, ClientTransport};
     use trustfall::provider::{
-        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator,
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters,
+        ResolveEdgeInfo, VertexIterator,
     };
 
-    use super::super::vertex::Vertex;
-    use crate::{adapter::AdapterInner, pagination::PaginationIterator};
+    use super::super::{hints, vertex::Vertex};
+    use crate::{
+        adapter::{AdapterInner, ErrorContext},
+        pagination::PaginationIterator,
+    };
 
     pub(super) fn series<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::SERIES_FILTERS,
+            super::TimeField::BeginAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_league()
-                .expect("conversion failed, vertex was not a League");
+            let Some(vertex) = vertex.as_league() else {
+                adapter.report(vertex.mismatch("League"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::leagues::ListLeagueSeries::builder().id(id).build(),
-                )
-                .map(Vertex::Series),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::leagues::ListLeagueSeries::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("League.series").vertex(id),
+                    )
+                    .map(Vertex::Series),
+                ),
             )
         })
     }
@@ -60,15 +88,14 @@ pub(super) fn resolve_series_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
-    _parameters: &EdgeParameters,
+    parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "tournaments" => series::tournaments(adapter, contexts),
+        "tournaments" => series::tournaments(adapter, contexts, parameters, resolve_info),
         "winner" => series::winner(adapter, contexts),
         "league" => series::league(adapter, contexts),
-        _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Series'")
-        }
+        _ => unknown_edge(&adapter, contexts, "Series", edge_name),
     }
 }
 
@@ -77,32 +104,52 @@ mod series {
 
     use pandascore::{endpoint::all, ClientTransport};
     use trustfall::provider::{
-        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator,
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters,
+        ResolveEdgeInfo, VertexIterator,
     };
 
     use crate::{
-        adapter::{edges::resolve_winner, AdapterInner, Vertex},
+        adapter::{hints, AdapterInner, ErrorContext, Vertex},
         pagination::PaginationIterator,
     };
 
     pub(super) fn tournaments<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::TOURNAMENT_FILTERS,
+            super::TimeField::BeginAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_series()
-                .expect("conversion failed, vertex was not a Series");
+            let Some(vertex) = vertex.as_series() else {
+                adapter.report(vertex.mismatch("Series"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::series::ListSeriesTournaments::builder().id(id).build(),
-                )
-                .map(|x| Vertex::Tournament(Box::new(x))),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::series::ListSeriesTournaments::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Series.tournaments").vertex(id),
+                    )
+                    .map(|x| Vertex::Tournament(Box::new(x))),
+                ),
             )
         })
     }
@@ -114,13 +161,17 @@ mod series {
     where
         V: AsVertex<Vertex> + 'a,
     {
-        resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_series()
-                .expect("conversion failed, vertex was not a Series");
-            let adapter = Arc::clone(&adapter);
-            resolve_winner(&vertex.winner, adapter)
-        })
+        super::resolve_winners(
+            adapter,
+            contexts,
+            ErrorContext::at("Series.winner"),
+            |vertex| {
+                let vertex = vertex
+                    .as_series()
+                    .ok_or_else(|| vertex.mismatch("Series"))?;
+                Ok(vertex.winner.clone())
+            },
+        )
     }
 
     pub(super) fn league<'a, V>(
@@ -130,18 +181,18 @@ mod series {
     where
         V: AsVertex<Vertex> + 'a,
     {
-        resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_series()
-                .expect("conversion failed, vertex was not a Series");
-            let adapter = Arc::clone(&adapter);
-            Box::new(
-                adapter
-                    .execute(all::leagues::GetLeague::from(vertex.league.id))
-                    .map(Vertex::League)
-                    .into_iter(),
-            )
-        })
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("Series.league"),
+            Vertex::League,
+            |vertex| {
+                let vertex = vertex
+                    .as_series()
+                    .ok_or_else(|| vertex.mismatch("Series"))?;
+                Ok(Some(vertex.league.id))
+            },
+        )
     }
 }
 
@@ -149,54 +200,76 @@ pub(super) fn resolve_tournament_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
-    _parameters: &EdgeParameters,
+    parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "teams" => tournament::teams(adapter, contexts),
-        "matches" => tournament::matches(adapter, contexts),
-        "video_game" => tournament::video_game(contexts),
+        "teams" => tournament::teams(adapter, contexts, parameters, resolve_info),
+        "matches" => tournament::matches(adapter, contexts, parameters, resolve_info),
+        "video_game" => tournament::video_game(adapter, contexts),
         "winner" => tournament::winner(adapter, contexts),
         "league" => tournament::league(adapter, contexts),
         "series" => tournament::series(adapter, contexts),
-        _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Tournament'")
-        }
+        "rosters" => tournament::rosters(adapter, contexts),
+        _ => unknown_edge(&adapter, contexts, "Tournament", edge_name),
     }
 }
 
 mod tournament {
     use std::sync::Arc;
 
-    use pandascore::{endpoint::all, ClientTransport};
+    use pandascore::{
+        endpoint::all,
+        model::{player::CompactPlayer, tournament::TournamentRosters},
+        ClientTransport,
+    };
     use trustfall::provider::{
-        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator,
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters,
+        ResolveEdgeInfo, VertexIterator,
     };
 
     use crate::{
-        adapter::{edges::resolve_winner, AdapterInner, Vertex},
+        adapter::{hints, AdapterInner, ErrorContext, Vertex},
         pagination::PaginationIterator,
     };
 
     pub(super) fn teams<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::TEAM_FILTERS,
+            super::TimeField::ModifiedAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_tournament()
-                .expect("conversion failed, vertex was not a Tournament");
+            let Some(vertex) = vertex.as_tournament() else {
+                adapter.report(vertex.mismatch("Tournament"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::tournament::ListTournamentTeams::builder()
-                        .id(id)
-                        .build(),
-                )
-                .map(Vertex::Team),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::tournament::ListTournamentTeams::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Tournament.teams").vertex(id),
+                    )
+                    .map(Vertex::Team),
+                ),
             )
         })
     }
@@ -204,37 +277,56 @@ mod tournament {
     pub(super) fn matches<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::MATCH_FILTERS,
+            super::TimeField::BeginAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_tournament()
-                .expect("conversion failed, vertex was not a Tournament");
+            let Some(vertex) = vertex.as_tournament() else {
+                adapter.report(vertex.mismatch("Tournament"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::tournament::ListTournamentMatches::builder()
-                        .id(id)
-                        .build(),
-                )
-                .map(|x| Vertex::Match(Box::new(x))),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::tournament::ListTournamentMatches::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Tournament.matches").vertex(id),
+                    )
+                    .map(|x| Vertex::Match(Box::new(x))),
+                ),
             )
         })
     }
 
     pub(super) fn video_game<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_tournament()
-                .expect("conversion failed, vertex was not a Tournament");
+            let Some(vertex) = vertex.as_tournament() else {
+                adapter.report(vertex.mismatch("Tournament"));
+                return Box::new(std::iter::empty());
+            };
             Box::new(std::iter::once(Vertex::VideoGame(
                 vertex.video_game.clone(),
             )))
@@ -248,13 +340,17 @@ mod tournament {
     where
         V: AsVertex<Vertex> + 'a,
     {
-        resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_tournament()
-                .expect("conversion failed, vertex was not a Tournament");
-            let adapter = Arc::clone(&adapter);
-            resolve_winner(&vertex.winner, adapter)
-        })
+        super::resolve_winners(
+            adapter,
+            contexts,
+            ErrorContext::at("Tournament.winner"),
+            |vertex| {
+                let vertex = vertex
+                    .as_tournament()
+                    .ok_or_else(|| vertex.mismatch("Tournament"))?;
+                Ok(vertex.winner.clone())
+            },
+        )
     }
 
     pub(super) fn league<'a, V>(
@@ -264,38 +360,74 @@ mod tournament {
     where
         V: AsVertex<Vertex> + 'a,
     {
-        resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_tournament()
-                .expect("conversion failed, vertex was not a Tournament");
-            let adapter = Arc::clone(&adapter);
-            Box::new(
-                adapter
-                    .execute(all::leagues::GetLeague::from(vertex.league.id))
-                    .map(Vertex::League)
-                    .into_iter(),
-            )
-        })
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("Tournament.league"),
+            Vertex::League,
+            |vertex| {
+                let vertex = vertex
+                    .as_tournament()
+                    .ok_or_else(|| vertex.mismatch("Tournament"))?;
+                Ok(Some(vertex.league.id))
+            },
+        )
     }
 
     pub(super) fn series<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("Tournament.series"),
+            Vertex::Series,
+            |vertex| {
+                let vertex = vertex
+                    .as_tournament()
+                    .ok_or_else(|| vertex.mismatch("Tournament"))?;
+                Ok(Some(vertex.serie.id))
+            },
+        )
+    }
+
+    /// Fetches the rosters of each tournament on its own, since the API only serves them per
+    /// tournament and has no list endpoint that could batch them.
+    pub(super) fn rosters<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_tournament()
-                .expect("conversion failed, vertex was not a Tournament");
-            let adapter = Arc::clone(&adapter);
-            Box::new(
-                adapter
-                    .execute(all::series::GetSeries::from(vertex.serie.id))
-                    .map(Vertex::Series)
-                    .into_iter(),
-            )
+            let Some(vertex) = vertex.as_tournament() else {
+                adapter.report(vertex.mismatch("Tournament"));
+                return Box::new(std::iter::empty());
+            };
+            let rosters = adapter.execute(
+                all::tournament::GetTournamentRosters::from(vertex.id),
+                ErrorContext::at("Tournament.rosters").vertex(vertex.id),
+            );
+            match rosters {
+                Some(TournamentRosters::Team(teams)) => {
+                    Box::new(teams.into_iter().map(|team| Vertex::Roster {
+                        players: team.players.clone(),
+                        team: Some(team),
+                    }))
+                }
+                Some(TournamentRosters::Player(players)) => {
+                    Box::new(players.into_iter().map(|player| Vertex::Roster {
+                        team: None,
+                        players: vec![CompactPlayer::from(player)],
+                    }))
+                }
+                _ => Box::new(std::iter::empty()),
+            }
         })
     }
 }
@@ -304,17 +436,16 @@ pub(super) fn resolve_team_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
-    _parameters: &EdgeParameters,
+    parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
         "players" => team::players(adapter, contexts),
-        "leagues" => team::leagues(adapter, contexts),
-        "series" => team::series(adapter, contexts),
-        "tournaments" => team::tournaments(adapter, contexts),
-        "matches" => team::matches(adapter, contexts),
-        _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Team'")
-        }
+        "leagues" => team::leagues(adapter, contexts, parameters, resolve_info),
+        "series" => team::series(adapter, contexts, parameters, resolve_info),
+        "tournaments" => team::tournaments(adapter, contexts, parameters, resolve_info),
+        "matches" => team::matches(adapter, contexts, parameters, resolve_info),
+        _ => unknown_edge(&adapter, contexts, "Team", edge_name),
     }
 }
 
@@ -323,11 +454,12 @@ mod team {
 
     use pandascore::{endpoint::all, ClientTransport};
     use trustfall::provider::{
-        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator,
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters,
+        ResolveEdgeInfo, VertexIterator,
     };
 
     use crate::{
-        adapter::{AdapterInner, Vertex},
+        adapter::{hints, AdapterInner, ErrorContext, Vertex},
         pagination::PaginationIterator,
     };
 
@@ -338,40 +470,55 @@ mod team {
     where
         V: AsVertex<Vertex> + 'a,
     {
-        resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_team()
-                .expect("conversion failed, vertex was not a Series");
-            let adapter = Arc::clone(&adapter);
-            Box::new(
-                vertex
-                    .players
-                    .clone()
-                    .into_iter()
-                    .filter_map(move |x| adapter.execute(all::players::GetPlayer::from(x.id)))
-                    .map(Vertex::Player),
-            )
-        })
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("Team.players"),
+            Vertex::Player,
+            |vertex| {
+                let vertex = vertex.as_team().ok_or_else(|| vertex.mismatch("Team"))?;
+                Ok(vertex.players.iter().map(|x| x.id).collect::<Vec<_>>())
+            },
+        )
     }
 
     pub(super) fn leagues<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::LEAGUE_FILTERS,
+            super::TimeField::ModifiedAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_team()
-                .expect("conversion failed, vertex was not a Team");
+            let Some(vertex) = vertex.as_team() else {
+                adapter.report(vertex.mismatch("Team"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::teams::ListTeamLeagues::builder().id(id).build(),
-                )
-                .map(Vertex::League),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::teams::ListTeamLeagues::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Team.leagues").vertex(id),
+                    )
+                    .map(Vertex::League),
+                ),
             )
         })
     }
@@ -379,21 +526,40 @@ mod team {
     pub(super) fn series<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::SERIES_FILTERS,
+            super::TimeField::BeginAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_team()
-                .expect("conversion failed, vertex was not a Team");
+            let Some(vertex) = vertex.as_team() else {
+                adapter.report(vertex.mismatch("Team"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::teams::ListTeamSeries::builder().id(id).build(),
-                )
-                .map(Vertex::Series),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::teams::ListTeamSeries::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Team.series").vertex(id),
+                    )
+                    .map(Vertex::Series),
+                ),
             )
         })
     }
@@ -401,21 +567,40 @@ mod team {
     pub(super) fn tournaments<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::TOURNAMENT_FILTERS,
+            super::TimeField::BeginAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_team()
-                .expect("conversion failed, vertex was not a Team");
+            let Some(vertex) = vertex.as_team() else {
+                adapter.report(vertex.mismatch("Team"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::teams::ListTeamTournaments::builder().id(id).build(),
-                )
-                .map(|x| Vertex::Tournament(Box::new(x))),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::teams::ListTeamTournaments::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Team.tournaments").vertex(id),
+                    )
+                    .map(|x| Vertex::Tournament(Box::new(x))),
+                ),
             )
         })
     }
@@ -423,21 +608,40 @@ mod team {
     pub(super) fn matches<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::MATCH_FILTERS,
+            super::TimeField::BeginAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_team()
-                .expect("conversion failed, vertex was not a Team");
+            let Some(vertex) = vertex.as_team() else {
+                adapter.report(vertex.mismatch("Team"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::teams::ListTeamMatches::builder().id(id).build(),
-                )
-                .map(|x| Vertex::Match(Box::new(x))),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::teams::ListTeamMatches::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Team.matches").vertex(id),
+                    )
+                    .map(|x| Vertex::Match(Box::new(x))),
+                ),
             )
         })
     }
@@ -447,17 +651,16 @@ pub(super) fn resolve_player_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
-    _parameters: &EdgeParameters,
+    parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
         "current_team" => player::current_team(adapter, contexts),
-        "leagues" => player::leagues(adapter, contexts),
-        "series" => player::series(adapter, contexts),
-        "tournaments" => player::tournaments(adapter, contexts),
-        "matches" => player::matches(adapter, contexts),
-        _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Player'")
-        }
+        "leagues" => player::leagues(adapter, contexts, parameters, resolve_info),
+        "series" => player::series(adapter, contexts, parameters, resolve_info),
+        "tournaments" => player::tournaments(adapter, contexts, parameters, resolve_info),
+        "matches" => player::matches(adapter, contexts, parameters, resolve_info),
+        _ => unknown_edge(&adapter, contexts, "Player", edge_name),
     }
 }
 
@@ -466,11 +669,12 @@ mod player {
 
     use pandascore::{endpoint::all, ClientTransport};
     use trustfall::provider::{
-        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator,
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters,
+        ResolveEdgeInfo, VertexIterator,
     };
 
     use crate::{
-        adapter::{AdapterInner, Vertex},
+        adapter::{hints, AdapterInner, ErrorContext, Vertex},
         pagination::PaginationIterator,
     };
 
@@ -481,41 +685,57 @@ mod player {
     where
         V: AsVertex<Vertex> + 'a,
     {
-        resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_player()
-                .expect("conversion failed, vertex was not a Player");
-            let adapter = Arc::clone(&adapter);
-            let id = vertex.current_team.as_ref().map(|t| t.id);
-            Box::new(
-                id.and_then(|id| {
-                    adapter
-                        .execute(all::teams::GetTeam::from(id))
-                        .map(Vertex::Team)
-                })
-                .into_iter(),
-            )
-        })
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("Player.current_team"),
+            Vertex::Team,
+            |vertex| {
+                let vertex = vertex
+                    .as_player()
+                    .ok_or_else(|| vertex.mismatch("Player"))?;
+                Ok(vertex.current_team.as_ref().map(|t| t.id))
+            },
+        )
     }
 
     pub(super) fn leagues<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::LEAGUE_FILTERS,
+            super::TimeField::ModifiedAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_player()
-                .expect("conversion failed, vertex was not a Player");
+            let Some(vertex) = vertex.as_player() else {
+                adapter.report(vertex.mismatch("Player"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::players::ListPlayerLeagues::builder().id(id).build(),
-                )
-                .map(Vertex::League),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::players::ListPlayerLeagues::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Player.leagues").vertex(id),
+                    )
+                    .map(Vertex::League),
+                ),
             )
         })
     }
@@ -523,21 +743,40 @@ mod player {
     pub(super) fn series<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::SERIES_FILTERS,
+            super::TimeField::BeginAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_player()
-                .expect("conversion failed, vertex was not a Player");
+            let Some(vertex) = vertex.as_player() else {
+                adapter.report(vertex.mismatch("Player"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::players::ListPlayerSeries::builder().id(id).build(),
-                )
-                .map(Vertex::Series),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::players::ListPlayerSeries::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Player.series").vertex(id),
+                    )
+                    .map(Vertex::Series),
+                ),
             )
         })
     }
@@ -545,23 +784,40 @@ mod player {
     pub(super) fn tournaments<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::TOURNAMENT_FILTERS,
+            super::TimeField::BeginAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_player()
-                .expect("conversion failed, vertex was not a Player");
+            let Some(vertex) = vertex.as_player() else {
+                adapter.report(vertex.mismatch("Player"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::players::ListPlayerTournaments::builder()
-                        .id(id)
-                        .build(),
-                )
-                .map(|x| Vertex::Tournament(Box::new(x))),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::players::ListPlayerTournaments::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Player.tournaments").vertex(id),
+                    )
+                    .map(|x| Vertex::Tournament(Box::new(x))),
+                ),
             )
         })
     }
@@ -569,21 +825,40 @@ mod player {
     pub(super) fn matches<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
+        let edge = super::collection_edge(
+            &adapter,
+            parameters,
+            resolve_info,
+            hints::MATCH_FILTERS,
+            super::TimeField::BeginAt,
+        );
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_player()
-                .expect("conversion failed, vertex was not a Player");
+            let Some(vertex) = vertex.as_player() else {
+                adapter.report(vertex.mismatch("Player"));
+                return Box::new(std::iter::empty());
+            };
             let id = vertex.id;
+            let Some((params, options)) = &edge else {
+                return Box::new(std::iter::empty());
+            };
             Box::new(
-                PaginationIterator::new(
-                    Arc::clone(&adapter),
-                    all::players::ListPlayerMatches::builder().id(id).build(),
-                )
-                .map(|x| Vertex::Match(Box::new(x))),
+                params.restrict(
+                    PaginationIterator::new(
+                        Arc::clone(&adapter),
+                        all::players::ListPlayerMatches::builder()
+                            .id(id)
+                            .options(options.clone())
+                            .build(),
+                        ErrorContext::at("Player.matches").vertex(id),
+                    )
+                    .map(|x| Vertex::Match(Box::new(x))),
+                ),
             )
         })
     }
@@ -594,27 +869,28 @@ pub(super) fn resolve_match_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
+    _resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
         "winner" => r#match::winner(adapter, contexts),
         "league" => r#match::league(adapter, contexts),
         "series" => r#match::series(adapter, contexts),
         "tournament" => r#match::tournament(adapter, contexts),
-        _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Match'")
-        }
+        "opponents" => r#match::opponents(adapter, contexts),
+        "results" => r#match::results(adapter, contexts),
+        _ => unknown_edge(&adapter, contexts, "Match", edge_name),
     }
 }
 
 mod r#match {
     use std::sync::Arc;
 
-    use pandascore::{endpoint::all, ClientTransport};
+    use pandascore::{endpoint::all, model::matches::MatchOpponents, ClientTransport};
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator,
     };
 
-    use crate::adapter::{edges::resolve_winner, AdapterInner, Vertex};
+    use crate::adapter::{AdapterInner, ErrorContext, Vertex};
 
     pub(super) fn winner<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
@@ -623,13 +899,15 @@ mod r#match {
     where
         V: AsVertex<Vertex> + 'a,
     {
-        resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_match()
-                .expect("conversion failed, vertex was not a Match");
-            let adapter = Arc::clone(&adapter);
-            resolve_winner(&vertex.winner, adapter)
-        })
+        super::resolve_winners(
+            adapter,
+            contexts,
+            ErrorContext::at("Match.winner"),
+            |vertex| {
+                let vertex = vertex.as_match().ok_or_else(|| vertex.mismatch("Match"))?;
+                Ok(vertex.winner.clone())
+            },
+        )
     }
 
     pub(super) fn league<'a, V>(
@@ -639,42 +917,87 @@ mod r#match {
     where
         V: AsVertex<Vertex> + 'a,
     {
-        resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_match()
-                .expect("conversion failed, vertex was not a Match");
-            let adapter = Arc::clone(&adapter);
-            Box::new(
-                adapter
-                    .execute(all::leagues::GetLeague::from(vertex.league.id))
-                    .map(Vertex::League)
-                    .into_iter(),
-            )
-        })
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("Match.league"),
+            Vertex::League,
+            |vertex| {
+                let vertex = vertex.as_match().ok_or_else(|| vertex.mismatch("Match"))?;
+                Ok(Some(vertex.league.id))
+            },
+        )
     }
 
     pub(super) fn series<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("Match.series"),
+            Vertex::Series,
+            |vertex| {
+                let vertex = vertex.as_match().ok_or_else(|| vertex.mismatch("Match"))?;
+                Ok(Some(vertex.serie.id))
+            },
+        )
+    }
+
+    pub(super) fn tournament<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("Match.tournament"),
+            |x| Vertex::Tournament(Box::new(x)),
+            |vertex| {
+                let vertex = vertex.as_match().ok_or_else(|| vertex.mismatch("Match"))?;
+                Ok(Some(vertex.tournament_id))
+            },
+        )
+    }
+
+    /// Fetches the opponents of each match on its own, since the API only serves them per match
+    /// and has no list endpoint that could batch them.
+    pub(super) fn opponents<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_match()
-                .expect("conversion failed, vertex was not a Match");
-            let adapter = Arc::clone(&adapter);
-            Box::new(
-                adapter
-                    .execute(all::series::GetSeries::from(vertex.serie.id))
-                    .map(Vertex::Series)
-                    .into_iter(),
-            )
+            let Some(vertex) = vertex.as_match() else {
+                adapter.report(vertex.mismatch("Match"));
+                return Box::new(std::iter::empty());
+            };
+            let opponents = adapter.execute(
+                all::matches::GetMatchOpponents::from(vertex.id),
+                ErrorContext::at("Match.opponents").vertex(vertex.id),
+            );
+            match opponents {
+                Some(MatchOpponents::Team(teams)) => {
+                    Box::new(teams.into_iter().map(Vertex::OpponentTeam))
+                }
+                Some(MatchOpponents::Player(players)) => {
+                    Box::new(players.into_iter().map(Vertex::OpponentPlayer))
+                }
+                _ => Box::new(std::iter::empty()),
+            }
         })
     }
 
-    pub(super) fn tournament<'a, V>(
+    pub(super) fn results<'a, V>(
         adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
@@ -682,109 +1005,604 @@ mod r#match {
         V: AsVertex<Vertex> + 'a,
     {
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_match()
-                .expect("conversion failed, vertex was not a Match");
-            let adapter = Arc::clone(&adapter);
-            Box::new(
-                adapter
-                    .execute(all::tournament::GetTournament::from(vertex.tournament_id))
-                    .map(|t| Vertex::Tournament(Box::new(t)))
-                    .into_iter(),
-            )
+            let Some(vertex) = vertex.as_match() else {
+                adapter.report(vertex.mismatch("Match"));
+                return Box::new(std::iter::empty());
+            };
+            Box::new(vertex.results.clone().into_iter().map(Vertex::MatchResult))
         })
     }
 }
 
 pub(super) fn resolve_winner_team_edge<'a, V: AsVertex<Vertex> + 'a>(
-    _adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
+    _resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "team" => winner_team::team(contexts),
-        _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Match'")
-        }
+        "team" => winner_team::team(adapter, contexts),
+        _ => unknown_edge(&adapter, contexts, "WinnerTeam", edge_name),
     }
 }
 
 mod winner_team {
+    use std::sync::Arc;
+
+    use pandascore::ClientTransport;
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator,
     };
 
-    use crate::adapter::Vertex;
+    use crate::adapter::{AdapterInner, Vertex};
 
     pub(super) fn team<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_winner_team()
-                .expect("conversion failed, vertex was not a WinnerTeam");
+            let Some(vertex) = vertex.as_winner_team() else {
+                adapter.report(vertex.mismatch("WinnerTeam"));
+                return Box::new(std::iter::empty());
+            };
             Box::new(std::iter::once(Vertex::Team(vertex.1.clone())))
         })
     }
 }
 
 pub(super) fn resolve_winner_player_edge<'a, V: AsVertex<Vertex> + 'a>(
-    _adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
+    _resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "player" => crate::adapter::edges::winner_player::player(contexts),
-        _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Match'")
-        }
+        "player" => crate::adapter::edges::winner_player::player(adapter, contexts),
+        _ => unknown_edge(&adapter, contexts, "WinnerPlayer", edge_name),
     }
 }
 
 mod winner_player {
+    use std::sync::Arc;
+
+    use pandascore::ClientTransport;
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator,
     };
 
-    use crate::adapter::Vertex;
+    use crate::adapter::{AdapterInner, Vertex};
 
     pub(super) fn player<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
         contexts: ContextIterator<'a, V>,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
     where
         V: AsVertex<Vertex> + 'a,
     {
         resolve_neighbors_with(contexts, move |vertex| {
-            let vertex = vertex
-                .as_winner_player()
-                .expect("conversion failed, vertex was not a WinnerTeam");
+            let Some(vertex) = vertex.as_winner_player() else {
+                adapter.report(vertex.mismatch("WinnerPlayer"));
+                return Box::new(std::iter::empty());
+            };
             Box::new(std::iter::once(Vertex::Player(vertex.1.clone())))
         })
     }
 }
 
-fn resolve_winner<'a>(
-    winner: &Option<Winner>,
-    adapter: Arc<AdapterInner<impl ClientTransport>>,
-) -> VertexIterator<'a, Vertex> {
-    match winner {
-        Some(Winner::Player { id: Some(id), .. }) => Box::new(
-            adapter
-                .execute(all::players::GetPlayer::from(*id))
-                .map(|player| Vertex::WinnerPlayer { player, id: *id })
-                .into_iter(),
-        ),
-        Some(Winner::Team { id: Some(id), .. }) => Box::new(
-            adapter
-                .execute(all::teams::GetTeam::from(*id))
-                .map(|team| Vertex::WinnerTeam { team, id: *id })
-                .into_iter(),
-        ),
-        _ => Box::new(std::iter::empty()),
+pub(super) fn resolve_video_game_edge<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "leagues" => video_game::leagues(adapter, contexts, resolve_info),
+        "series" => video_game::series(adapter, contexts, resolve_info),
+        "tournaments" => video_game::tournaments(adapter, contexts, resolve_info),
+        "teams" => video_game::teams(adapter, contexts, resolve_info),
+        "players" => video_game::players(adapter, contexts, resolve_info),
+        _ => unknown_edge(&adapter, contexts, "VideoGame", edge_name),
+    }
+}
+
+mod video_game {
+    use std::sync::Arc;
+
+    use pandascore::ClientTransport;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::adapter::{
+        entrypoints, params::EntrypointParams, AdapterInner, ErrorContext, Vertex,
+    };
+
+    pub(super) fn leagues<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+        resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        let info = resolve_info.destination();
+        resolve_neighbors_with(contexts, move |vertex| {
+            let Some(vertex) = vertex.as_video_game() else {
+                adapter.report(vertex.mismatch("VideoGame"));
+                return Box::new(std::iter::empty());
+            };
+            entrypoints::leagues(
+                Arc::clone(&adapter),
+                ErrorContext::at("VideoGame.leagues").vertex(vertex.id),
+                &EntrypointParams::for_video_game(vertex.id),
+                &info,
+            )
+        })
+    }
+
+    pub(super) fn series<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+        resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        let info = resolve_info.destination();
+        resolve_neighbors_with(contexts, move |vertex| {
+            let Some(vertex) = vertex.as_video_game() else {
+                adapter.report(vertex.mismatch("VideoGame"));
+                return Box::new(std::iter::empty());
+            };
+            entrypoints::series(
+                Arc::clone(&adapter),
+                ErrorContext::at("VideoGame.series").vertex(vertex.id),
+                &EntrypointParams::for_video_game(vertex.id),
+                &info,
+            )
+        })
+    }
+
+    pub(super) fn tournaments<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+        resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        let info = resolve_info.destination();
+        resolve_neighbors_with(contexts, move |vertex| {
+            let Some(vertex) = vertex.as_video_game() else {
+                adapter.report(vertex.mismatch("VideoGame"));
+                return Box::new(std::iter::empty());
+            };
+            entrypoints::tournaments(
+                Arc::clone(&adapter),
+                ErrorContext::at("VideoGame.tournaments").vertex(vertex.id),
+                &EntrypointParams::for_video_game(vertex.id),
+                &info,
+            )
+        })
+    }
+
+    pub(super) fn teams<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+        resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        let info = resolve_info.destination();
+        resolve_neighbors_with(contexts, move |vertex| {
+            let Some(vertex) = vertex.as_video_game() else {
+                adapter.report(vertex.mismatch("VideoGame"));
+                return Box::new(std::iter::empty());
+            };
+            entrypoints::teams(
+                Arc::clone(&adapter),
+                ErrorContext::at("VideoGame.teams").vertex(vertex.id),
+                &EntrypointParams::for_video_game(vertex.id),
+                &info,
+            )
+        })
+    }
+
+    pub(super) fn players<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+        resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        let info = resolve_info.destination();
+        resolve_neighbors_with(contexts, move |vertex| {
+            let Some(vertex) = vertex.as_video_game() else {
+                adapter.report(vertex.mismatch("VideoGame"));
+                return Box::new(std::iter::empty());
+            };
+            entrypoints::players(
+                Arc::clone(&adapter),
+                ErrorContext::at("VideoGame.players").vertex(vertex.id),
+                &EntrypointParams::for_video_game(vertex.id),
+                &info,
+            )
+        })
+    }
+}
+
+pub(super) fn resolve_match_result_edge<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    _resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "team" => match_result::team(adapter, contexts),
+        "player" => match_result::player(adapter, contexts),
+        _ => unknown_edge(&adapter, contexts, "MatchResult", edge_name),
+    }
+}
+
+mod match_result {
+    use std::sync::Arc;
+
+    use pandascore::{model::matches::MatchResult, ClientTransport};
+    use trustfall::provider::{AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator};
+
+    use crate::adapter::{AdapterInner, ErrorContext, Vertex};
+
+    pub(super) fn team<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("MatchResult.team"),
+            Vertex::Team,
+            |vertex| {
+                let vertex = vertex
+                    .as_match_result()
+                    .ok_or_else(|| vertex.mismatch("MatchResult"))?;
+                Ok(match vertex {
+                    MatchResult::Team { team_id, .. } => Some(*team_id),
+                    _ => None,
+                })
+            },
+        )
+    }
+
+    pub(super) fn player<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("MatchResult.player"),
+            Vertex::Player,
+            |vertex| {
+                let vertex = vertex
+                    .as_match_result()
+                    .ok_or_else(|| vertex.mismatch("MatchResult"))?;
+                Ok(match vertex {
+                    MatchResult::Player { player_id, .. } => Some(*player_id),
+                    _ => None,
+                })
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_roster_edge<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    _resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "team" => roster::team(adapter, contexts),
+        "players" => roster::players(adapter, contexts),
+        _ => unknown_edge(&adapter, contexts, "Roster", edge_name),
+    }
+}
+
+mod roster {
+    use std::sync::Arc;
+
+    use pandascore::ClientTransport;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator,
+    };
+
+    use crate::adapter::{AdapterInner, ErrorContext, Vertex};
+
+    pub(super) fn team<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let Some((team, _)) = vertex.as_roster() else {
+                adapter.report(vertex.mismatch("Roster"));
+                return Box::new(std::iter::empty());
+            };
+            Box::new(team.clone().map(Vertex::Team).into_iter())
+        })
+    }
+
+    pub(super) fn players<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("Roster.players"),
+            Vertex::Player,
+            |vertex| {
+                let (_, players) = vertex
+                    .as_roster()
+                    .ok_or_else(|| vertex.mismatch("Roster"))?;
+                Ok(players.iter().map(|x| x.id).collect::<Vec<_>>())
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_opponent_team_edge<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    _resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "team" => opponent_team::team(adapter, contexts),
+        _ => unknown_edge(&adapter, contexts, "OpponentTeam", edge_name),
+    }
+}
+
+mod opponent_team {
+    use std::sync::Arc;
+
+    use pandascore::ClientTransport;
+    use trustfall::provider::{AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator};
+
+    use crate::adapter::{AdapterInner, ErrorContext, Vertex};
+
+    pub(super) fn team<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("OpponentTeam.team"),
+            Vertex::Team,
+            |vertex| {
+                let vertex = vertex
+                    .as_opponent_team()
+                    .ok_or_else(|| vertex.mismatch("OpponentTeam"))?;
+                Ok(Some(vertex.id))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_opponent_player_edge<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    _resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "player" => opponent_player::player(adapter, contexts),
+        _ => unknown_edge(&adapter, contexts, "OpponentPlayer", edge_name),
+    }
+}
+
+mod opponent_player {
+    use std::sync::Arc;
+
+    use pandascore::ClientTransport;
+    use trustfall::provider::{AsVertex, ContextIterator, ContextOutcomeIterator, VertexIterator};
+
+    use crate::adapter::{AdapterInner, ErrorContext, Vertex};
+
+    pub(super) fn player<'a, V>(
+        adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+        contexts: ContextIterator<'a, V>,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+    where
+        V: AsVertex<Vertex> + 'a,
+    {
+        super::resolve_batched(
+            adapter,
+            contexts,
+            ErrorContext::at("OpponentPlayer.player"),
+            Vertex::Player,
+            |vertex| {
+                let vertex = vertex
+                    .as_opponent_player()
+                    .ok_or_else(|| vertex.mismatch("OpponentPlayer"))?;
+                Ok(Some(vertex.id))
+            },
+        )
     }
 }
+
+/// Parses a collection edge's parameters and builds the options for listing its neighbors.
+///
+/// Returns `None` if a parameter is invalid or no neighbor can satisfy the query.
+fn collection_edge<T: ClientTransport>(
+    adapter: &AdapterInner<T>,
+    parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    fields: &[(&str, &str)],
+    time: TimeField,
+) -> Option<(CollectionParams, CollectionOptions)> {
+    let params = CollectionParams::parse(adapter, parameters, time)?;
+    let options = params.apply(hints::edge_options(resolve_info, fields, &params.pinned())?);
+    Some((params, options))
+}
+
+/// Resolves an edge to entities fetched by id, fetching the neighbors of a whole chunk of
+/// contexts together instead of sending one request per vertex.
+///
+/// Contexts keep their order, and each yields its neighbors in the order `ids` lists them.
+fn resolve_batched<'a, V, T, E, I>(
+    adapter: Arc<AdapterInner<T>>,
+    contexts: ContextIterator<'a, V>,
+    context: ErrorContext,
+    f: fn(E) -> Vertex,
+    ids: impl Fn(&Vertex) -> Result<I, AdapterError> + 'a,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+where
+    V: AsVertex<Vertex> + 'a,
+    T: ClientTransport + 'a,
+    E: Cached + Clone + 'a,
+    I: IntoIterator<Item = u64>,
+{
+    let chunks = chunks(Arc::clone(&adapter), contexts, move |vertex| {
+        ids(vertex).map(|ids| ids.into_iter().collect::<Vec<_>>())
+    });
+
+    Box::new(chunks.flat_map(move |chunk| {
+        let all: Vec<u64> = chunk
+            .iter()
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect();
+        let found = adapter.fetch_many::<E>(&all, &context);
+        chunk.into_iter().map(move |(ctx, ids)| {
+            let neighbors: Vec<Vertex> = ids
+                .iter()
+                .filter_map(|id| found.get(id).cloned())
+                .map(f)
+                .collect();
+            let neighbors: VertexIterator<'a, Vertex> = Box::new(neighbors.into_iter());
+            (ctx, neighbors)
+        })
+    }))
+}
+
+/// Resolves a `winner` edge to the winning team or player, fetching the winners of a whole chunk
+/// of contexts together like [`resolve_batched`].
+fn resolve_winners<'a, V, T>(
+    adapter: Arc<AdapterInner<T>>,
+    contexts: ContextIterator<'a, V>,
+    context: ErrorContext,
+    winner: impl Fn(&Vertex) -> Result<Option<Winner>, AdapterError> + 'a,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>>
+where
+    V: AsVertex<Vertex> + 'a,
+    T: ClientTransport + 'a,
+{
+    let chunks = chunks(Arc::clone(&adapter), contexts, winner);
+
+    Box::new(chunks.flat_map(move |chunk| {
+        let (mut players, mut teams) = (Vec::new(), Vec::new());
+        for (_, winner) in &chunk {
+            match winner {
+                Some(Winner::Player { id: Some(id), .. }) => players.push(*id),
+                Some(Winner::Team { id: Some(id), .. }) => teams.push(*id),
+                _ => {}
+            }
+        }
+        let players = adapter.fetch_many(&players, &context);
+        let teams = adapter.fetch_many(&teams, &context);
+        chunk.into_iter().map(move |(ctx, winner)| {
+            let neighbor = match winner {
+                Some(Winner::Player { id: Some(id), .. }) => players
+                    .get(&id)
+                    .cloned()
+                    .map(|player| Vertex::WinnerPlayer { player, id }),
+                Some(Winner::Team { id: Some(id), .. }) => teams
+                    .get(&id)
+                    .cloned()
+                    .map(|team| Vertex::WinnerTeam { team, id }),
+                _ => None,
+            };
+            let neighbors: VertexIterator<'a, Vertex> = Box::new(neighbor.into_iter());
+            (ctx, neighbors)
+        })
+    }))
+}
+
+/// Splits `contexts` into chunks of up to a page, pairing each context with what `key` reads off
+/// its vertex.
+///
+/// Contexts without a vertex, or whose key can't be read, get the default key.
+fn chunks<'a, V, T, K>(
+    adapter: Arc<AdapterInner<T>>,
+    mut contexts: ContextIterator<'a, V>,
+    key: impl Fn(&Vertex) -> Result<K, AdapterError> + 'a,
+) -> impl Iterator<Item = Vec<(DataContext<V>, K)>> + 'a
+where
+    V: AsVertex<Vertex> + 'a,
+    T: ClientTransport + 'a,
+    K: Default + 'a,
+{
+    std::iter::from_fn(move || {
+        let chunk: Vec<_> = contexts
+            .by_ref()
+            .take(MAX_PAGE_SIZE)
+            .map(|ctx| {
+                let key = match ctx.active_vertex::<Vertex>().map(&key) {
+                    Some(Ok(key)) => key,
+                    Some(Err(e)) => {
+                        adapter.report(e);
+                        K::default()
+                    }
+                    None => K::default(),
+                };
+                (ctx, key)
+            })
+            .collect();
+        (!chunk.is_empty()).then_some(chunk)
+    })
+}
+
+/// Reports an edge the adapter doesn't know, resolving it to no neighbors.
+pub(super) fn unknown_edge<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: &AdapterInner<impl ClientTransport>,
+    contexts: ContextIterator<'a, V>,
+    type_name: &str,
+    edge_name: &str,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    adapter.report(AdapterError::UnknownEdge {
+        type_name: type_name.to_owned(),
+        edge: edge_name.to_owned(),
+    });
+    Box::new(contexts.map(|ctx| {
+        let neighbors: VertexIterator<'a, Vertex> = Box::new(std::iter::empty());
+        (ctx, neighbors)
+    }))
+}
@@ -1,197 +1,340 @@
 use std::sync::Arc;
 
+use compact_str::ToCompactString;
 use pandascore::{
-    endpoint::{all, lol, CollectionOptions},
+    endpoint::{all, lol, rl, CollectionOptions, ListResponse, PaginatedEndpoint},
     ClientTransport,
 };
-use trustfall::provider::VertexIterator;
+use trustfall::provider::{VertexInfo, VertexIterator};
 
 use super::vertex::Vertex;
 use crate::{
-    adapter::{error::AdapterError, AdapterInner},
+    adapter::{
+        cache::Cached, error::AdapterError, hints, params::EntrypointParams, AdapterInner,
+        ErrorContext,
+    },
     pagination::PaginationIterator,
 };
 
 pub(super) fn leagues<'a, T: ClientTransport + 'a>(
     adapter: Arc<AdapterInner<T>>,
-    game: Option<&str>,
-    search: Option<&str>,
+    context: ErrorContext,
+    params: &EntrypointParams,
+    info: &impl VertexInfo,
 ) -> VertexIterator<'a, Vertex> {
-    let mut init = CollectionOptions::new();
-    if let Some(search) = search {
-        init = init.search("name", search);
-    }
-
-    match game {
-        Some("lol") => Box::new(
-            PaginationIterator::new(adapter, lol::leagues::ListLeagues(init)).map(Vertex::League),
-        ),
-        Some(g) => {
-            adapter
-                .errors()
-                .push(AdapterError::InvalidGame(g.to_string()));
-            Box::new(std::iter::empty())
-        }
-        None => Box::new(
-            PaginationIterator::new(adapter, all::leagues::ListLeagues(init)).map(Vertex::League),
+    let vertices = match lookup(&adapter, params, info, hints::LEAGUE_FILTERS) {
+        Lookup::Impossible => return Box::new(std::iter::empty()),
+        Lookup::Get(id) => get(adapter, context, id, Vertex::League),
+        Lookup::List(Endpoints::Lol, init) => paginate(
+            adapter,
+            context,
+            lol::leagues::ListLeagues(*init),
+            Vertex::League,
         ),
-    }
+        Lookup::List(Endpoints::Rl, init) => paginate(
+            adapter,
+            context,
+            rl::leagues::ListLeagues(*init),
+            Vertex::League,
+        ),
+        Lookup::List(Endpoints::All, init) => paginate(
+            adapter,
+            context,
+            all::leagues::ListLeagues(*init),
+            Vertex::League,
+        ),
+    };
+
+    Box::new(params.restrict(vertices))
 }
 
 pub(super) fn series<'a, T: ClientTransport + 'a>(
     adapter: Arc<AdapterInner<T>>,
-    game: Option<&str>,
-    search: Option<&str>,
+    context: ErrorContext,
+    params: &EntrypointParams,
+    info: &impl VertexInfo,
 ) -> VertexIterator<'a, Vertex> {
-    let mut init = CollectionOptions::new();
-    if let Some(search) = search {
-        init = init.search("name", search);
-    }
-
-    match game {
-        Some("lol") => Box::new(
-            PaginationIterator::new(
-                adapter,
-                lol::series::ListSeries::builder().options(init).build(),
-            )
-            .map(Vertex::Series),
-        ),
-        Some(g) => {
-            adapter
-                .errors()
-                .push(AdapterError::InvalidGame(g.to_string()));
-            Box::new(std::iter::empty())
-        }
-        None => Box::new(
-            PaginationIterator::new(
-                adapter,
-                all::series::ListSeries::builder().options(init).build(),
-            )
-            .map(Vertex::Series),
+    let vertices = match lookup(&adapter, params, info, hints::SERIES_FILTERS) {
+        Lookup::Impossible => return Box::new(std::iter::empty()),
+        Lookup::Get(id) => get(adapter, context, id, Vertex::Series),
+        Lookup::List(Endpoints::Lol, init) => paginate(
+            adapter,
+            context,
+            lol::series::ListSeries::builder().options(*init).build(),
+            Vertex::Series,
         ),
-    }
+        Lookup::List(Endpoints::Rl, init) => paginate(
+            adapter,
+            context,
+            rl::series::ListSeries::builder().options(*init).build(),
+            Vertex::Series,
+        ),
+        Lookup::List(Endpoints::All, init) => paginate(
+            adapter,
+            context,
+            all::series::ListSeries::builder().options(*init).build(),
+            Vertex::Series,
+        ),
+    };
+
+    Box::new(params.restrict(vertices))
 }
 
 pub(super) fn tournaments<'a, T: ClientTransport + 'a>(
     adapter: Arc<AdapterInner<T>>,
-    game: Option<&str>,
-    search: Option<&str>,
+    context: ErrorContext,
+    params: &EntrypointParams,
+    info: &impl VertexInfo,
 ) -> VertexIterator<'a, Vertex> {
-    let mut init = CollectionOptions::new();
-    if let Some(search) = search {
-        init = init.search("name", search);
-    }
-
-    match game {
-        Some("lol") => Box::new(
-            PaginationIterator::new(
-                adapter,
-                lol::tournaments::ListTournaments::builder()
-                    .options(init)
-                    .build(),
-            )
-            .map(|x| Vertex::Tournament(Box::new(x))),
-        ),
-        Some(g) => {
-            adapter
-                .errors()
-                .push(AdapterError::InvalidGame(g.to_string()));
-            Box::new(std::iter::empty())
-        }
-        None => Box::new(
-            PaginationIterator::new(
-                adapter,
-                all::tournament::ListTournaments::builder()
-                    .options(init)
-                    .build(),
-            )
-            .map(|x| Vertex::Tournament(Box::new(x))),
+    let vertices = match lookup(&adapter, params, info, hints::TOURNAMENT_FILTERS) {
+        Lookup::Impossible => return Box::new(std::iter::empty()),
+        Lookup::Get(id) => get(adapter, context, id, |x| Vertex::Tournament(Box::new(x))),
+        Lookup::List(Endpoints::Lol, init) => paginate(
+            adapter,
+            context,
+            lol::tournaments::ListTournaments::builder()
+                .options(*init)
+                .build(),
+            |x| Vertex::Tournament(Box::new(x)),
         ),
-    }
+        Lookup::List(Endpoints::Rl, init) => paginate(
+            adapter,
+            context,
+            rl::tournaments::ListTournaments::builder()
+                .options(*init)
+                .build(),
+            |x| Vertex::Tournament(Box::new(x)),
+        ),
+        Lookup::List(Endpoints::All, init) => paginate(
+            adapter,
+            context,
+            all::tournament::ListTournaments::builder()
+                .options(*init)
+                .build(),
+            |x| Vertex::Tournament(Box::new(x)),
+        ),
+    };
+
+    Box::new(params.restrict(vertices))
 }
 
 pub(super) fn matches<'a, T: ClientTransport + 'a>(
     adapter: Arc<AdapterInner<T>>,
-    game: Option<&str>,
-    search: Option<&str>,
+    context: ErrorContext,
+    params: &EntrypointParams,
+    info: &impl VertexInfo,
 ) -> VertexIterator<'a, Vertex> {
-    let mut init = CollectionOptions::new();
-    if let Some(search) = search {
-        init = init.search("name", search);
-    }
-
-    match game {
-        Some("lol") => Box::new(
-            PaginationIterator::new(
-                adapter,
-                lol::matches::ListMatches::builder().options(init).build(),
-            )
-            .map(|x| Vertex::Match(Box::new(x))),
-        ),
-        Some(g) => {
-            adapter
-                .errors()
-                .push(AdapterError::InvalidGame(g.to_string()));
-            Box::new(std::iter::empty())
-        }
-        None => Box::new(
-            PaginationIterator::new(
-                adapter,
-                all::matches::ListMatches::builder().options(init).build(),
-            )
-            .map(|x| Vertex::Match(Box::new(x))),
+    let vertices = match lookup(&adapter, params, info, hints::MATCH_FILTERS) {
+        Lookup::Impossible => return Box::new(std::iter::empty()),
+        Lookup::Get(id) => get(adapter, context, id, |x| Vertex::Match(Box::new(x))),
+        Lookup::List(Endpoints::Lol, init) => paginate(
+            adapter,
+            context,
+            lol::matches::ListMatches::builder().options(*init).build(),
+            |x| Vertex::Match(Box::new(x)),
         ),
-    }
+        Lookup::List(Endpoints::Rl, init) => paginate(
+            adapter,
+            context,
+            rl::matches::ListMatches::builder().options(*init).build(),
+            |x| Vertex::Match(Box::new(x)),
+        ),
+        Lookup::List(Endpoints::All, init) => paginate(
+            adapter,
+            context,
+            all::matches::ListMatches::builder().options(*init).build(),
+            |x| Vertex::Match(Box::new(x)),
+        ),
+    };
+
+    Box::new(params.restrict(vertices))
 }
 
 pub(super) fn teams<'a, T: ClientTransport + 'a>(
     adapter: Arc<AdapterInner<T>>,
-    game: Option<&str>,
-    search: Option<&str>,
+    context: ErrorContext,
+    params: &EntrypointParams,
+    info: &impl VertexInfo,
 ) -> VertexIterator<'a, Vertex> {
-    let mut init = CollectionOptions::new();
-    if let Some(search) = search {
-        init = init.search("name", search);
-    }
-
-    match game {
-        Some("lol") => Box::new(
-            PaginationIterator::new(adapter, lol::teams::ListTeams(init)).map(Vertex::Team),
-        ),
-        Some(g) => {
-            adapter
-                .errors()
-                .push(AdapterError::InvalidGame(g.to_string()));
-            Box::new(std::iter::empty())
+    let vertices = match lookup(&adapter, params, info, hints::TEAM_FILTERS) {
+        Lookup::Impossible => return Box::new(std::iter::empty()),
+        Lookup::Get(id) => get(adapter, context, id, Vertex::Team),
+        Lookup::List(Endpoints::Lol, init) => {
+            paginate(adapter, context, lol::teams::ListTeams(*init), Vertex::Team)
         }
-        None => Box::new(
-            PaginationIterator::new(adapter, all::teams::ListTeams(init)).map(Vertex::Team),
-        ),
-    }
+        Lookup::List(Endpoints::Rl, init) => {
+            paginate(adapter, context, rl::teams::ListTeams(*init), Vertex::Team)
+        }
+        Lookup::List(Endpoints::All, init) => {
+            paginate(adapter, context, all::teams::ListTeams(*init), Vertex::Team)
+        }
+    };
+
+    Box::new(params.restrict(vertices))
 }
 
 pub(super) fn players<'a, T: ClientTransport + 'a>(
     adapter: Arc<AdapterInner<T>>,
-    game: Option<&str>,
-    search: Option<&str>,
+    context: ErrorContext,
+    params: &EntrypointParams,
+    info: &impl VertexInfo,
 ) -> VertexIterator<'a, Vertex> {
-    let mut init = CollectionOptions::new();
-    if let Some(search) = search {
-        init = init.search("name", search);
-    }
+    let vertices = match lookup(&adapter, params, info, hints::PLAYER_FILTERS) {
+        Lookup::Impossible => return Box::new(std::iter::empty()),
+        Lookup::Get(id) => get(adapter, context, id, Vertex::Player),
+        Lookup::List(Endpoints::Lol, init) => paginate(
+            adapter,
+            context,
+            lol::players::ListPlayers(*init),
+            Vertex::Player,
+        ),
+        Lookup::List(Endpoints::Rl, init) => paginate(
+            adapter,
+            context,
+            rl::players::ListPlayers(*init),
+            Vertex::Player,
+        ),
+        Lookup::List(Endpoints::All, init) => paginate(
+            adapter,
+            context,
+            all::players::ListPlayers(*init),
+            Vertex::Player,
+        ),
+    };
 
-    match game {
-        Some("lol") => Box::new(
-            PaginationIterator::new(adapter, lol::players::ListPlayers(init)).map(Vertex::Player),
-        ),
-        Some(g) => {
-            adapter
-                .errors()
-                .push(AdapterError::InvalidGame(g.to_string()));
-            Box::new(std::iter::empty())
+    Box::new(params.restrict(vertices))
+}
+
+pub(super) fn video_games<'a, T: ClientTransport + 'a>(
+    adapter: Arc<AdapterInner<T>>,
+) -> VertexIterator<'a, Vertex> {
+    // There is no video game endpoint, so read each video game off its first league.
+    Box::new(GAMES.iter().filter_map(move |&(_, id)| {
+        let options = CollectionOptions::new().per_page(1);
+        let context = ErrorContext::at("VideoGames");
+        let leagues = match id {
+            LOL => adapter.execute(lol::leagues::ListLeagues(options), context),
+            RL => adapter.execute(rl::leagues::ListLeagues(options), context),
+            _ => adapter.execute(
+                all::leagues::ListLeagues(options.filter("videogame", id.to_compact_string())),
+                context,
+            ),
+        }?;
+        leagues
+            .results
+            .into_iter()
+            .map(|league| league.video_game)
+            .find(|video_game| video_game.id == id)
+            .map(Vertex::VideoGame)
+    }))
+}
+
+/// The video game ids of the games with their own endpoints.
+const LOL: u64 = 1;
+const RL: u64 = 22;
+
+/// The games the `game` parameter accepts, as `(game, video game id)` pairs.
+const GAMES: &[(&str, u64)] = &[
+    ("codmw", 23),
+    ("csgo", 3),
+    ("dota2", 4),
+    ("fifa", 25),
+    ("kog", 27),
+    ("lol", LOL),
+    ("lol-wild-rift", 28),
+    ("ow", 14),
+    ("pubg", 20),
+    ("r6siege", 24),
+    ("rl", RL),
+    ("valorant", 26),
+];
+
+/// How an entrypoint should fetch its vertices.
+enum Lookup {
+    /// The query can't match any vertex.
+    Impossible,
+    /// The query only matches the vertex with this id.
+    Get(u64),
+    /// The query needs to list the collection from these endpoints with these options.
+    List(Endpoints, Box<CollectionOptions>),
+}
+
+/// The endpoints a collection is listed from.
+enum Endpoints {
+    /// The all-games endpoints, filtered by video game if a game was asked for.
+    All,
+    /// The League of Legends endpoints.
+    Lol,
+    /// The Rocket League endpoints.
+    Rl,
+}
+
+fn lookup<T: ClientTransport>(
+    adapter: &AdapterInner<T>,
+    params: &EntrypointParams,
+    info: &impl VertexInfo,
+    filters: &[(&str, &str)],
+) -> Lookup {
+    let game = match (params.video_game(), params.game()) {
+        (Some(id), _) => Some(id),
+        (None, None) => None,
+        (None, Some(game)) => match GAMES.iter().find(|(name, _)| *name == game) {
+            Some(&(_, id)) => Some(id),
+            None => {
+                adapter.report(AdapterError::InvalidGame(game.to_string()));
+                return Lookup::Impossible;
+            }
+        },
+    };
+
+    let ids = match params.ids(hints::required_ids(info)) {
+        Some(ids) if ids.is_empty() => return Lookup::Impossible,
+        // Direct gets ignore every other parameter, so they are only usable when none narrow.
+        Some(ids) if ids.len() == 1 && !params.narrows() => {
+            return Lookup::Get(ids[0]);
         }
-        None => Box::new(
-            PaginationIterator::new(adapter, all::players::ListPlayers(init)).map(Vertex::Player),
+        ids => ids,
+    };
+
+    let Some(init) = hints::apply_filters(params.options(ids), info, filters, &params.pinned())
+    else {
+        return Lookup::Impossible;
+    };
+    match game {
+        Some(LOL) => Lookup::List(Endpoints::Lol, Box::new(init)),
+        Some(RL) => Lookup::List(Endpoints::Rl, Box::new(init)),
+        // Games without their own endpoints are listed across all games, filtered by game.
+        Some(id) => Lookup::List(
+            Endpoints::All,
+            Box::new(init.filter("videogame", id.to_compact_string())),
         ),
+        None => Lookup::List(Endpoints::All, Box::new(init)),
     }
 }
+
+fn get<'a, T, E>(
+    adapter: Arc<AdapterInner<T>>,
+    context: ErrorContext,
+    id: u64,
+    f: fn(E) -> Vertex,
+) -> VertexIterator<'a, Vertex>
+where
+    T: ClientTransport + 'a,
+    E: Cached,
+{
+    Box::new(adapter.fetch(id, context).map(f).into_iter())
+}
+
+fn paginate<'a, T, E, I>(
+    adapter: Arc<AdapterInner<T>>,
+    context: ErrorContext,
+    init: E,
+    f: fn(I) -> Vertex,
+) -> VertexIterator<'a, Vertex>
+where
+    T: ClientTransport + 'a,
+    E: PaginatedEndpoint<Item = I, Response = ListResponse<I>> + Clone + std::fmt::Debug + 'a,
+    I: Cached + 'a,
+{
+    Box::new(PaginationIterator::new(adapter, init, context).map(f))
+}
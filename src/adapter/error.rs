@@ -1,7 +1,102 @@
+use std::fmt;
+
 #[derive(Debug, thiserror::Error)]
 pub enum AdapterError {
     #[error("invalid game: {0}")]
     InvalidGame(String),
-    #[error("failed to execute endpoint: {0}")]
-    EndpointError(#[from] pandascore::endpoint::EndpointError),
+    #[error("invalid value for parameter '{0}': {1}")]
+    InvalidParameter(&'static str, String),
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+    #[error("unknown property '{property}' on type '{type_name}'")]
+    UnknownProperty { type_name: String, property: String },
+    #[error("unknown edge '{edge}' on type '{type_name}'")]
+    UnknownEdge { type_name: String, edge: String },
+    #[error("expected a vertex of type '{expected}', got '{found}'")]
+    UnexpectedVertex {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error(
+        "requests can't block the current-thread runtime they are made from; run queries on \
+         another thread or use a multi-threaded runtime"
+    )]
+    CurrentThreadRuntime,
+    #[error("failed to execute endpoint ({context}): {source}")]
+    EndpointError {
+        source: pandascore::endpoint::EndpointError,
+        context: ErrorContext,
+    },
+}
+
+/// Where in a query a request failed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ErrorContext {
+    /// The entrypoint, such as `Matches`, or the type and edge, such as `Team.matches`, that
+    /// was being resolved.
+    pub location: String,
+    /// The id of the vertex whose edge was being resolved.
+    pub vertex_id: Option<u64>,
+    /// The path of the failed request, or the name of its endpoint if the URL isn't known.
+    pub endpoint: Option<String>,
+    /// The page of a paginated endpoint, starting at 1.
+    pub page: Option<u32>,
+}
+
+impl ErrorContext {
+    pub(crate) fn at(location: impl Into<String>) -> Self {
+        Self {
+            location: location.into(),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn vertex(mut self, id: u64) -> Self {
+        self.vertex_id = Some(id);
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.location)?;
+        if let Some(id) = self.vertex_id {
+            write!(f, " of vertex {id}")?;
+        }
+        if let Some(endpoint) = &self.endpoint {
+            write!(f, ", {endpoint}")?;
+        }
+        if let Some(page) = self.page {
+            write!(f, ", page {page}")?;
+        }
+        Ok(())
+    }
+}
+
+/// What the adapter does when a request fails, a query is given invalid input, or a schema asks
+/// for a property or edge the adapter doesn't know.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Records the error in [`Adapter::errors`](crate::Adapter::errors) and carries on, leaving
+    /// out whatever could not be fetched.
+    #[default]
+    Collect,
+    /// Stops fetching anything else and ends the query with the error, as returned by
+    /// [`Adapter::execute_query`](crate::Adapter::execute_query) and
+    /// [`AsyncAdapter::execute_query`](crate::AsyncAdapter::execute_query).
+    ///
+    /// Queries run with [`trustfall::execute_query`] can't be ended early, so they carry on
+    /// without fetching anything else: their remaining rows may be missing data. The error is
+    /// recorded in [`Adapter::errors`](crate::Adapter::errors) instead.
+    FailFast,
+    /// Panics with the error.
+    Panic,
 }
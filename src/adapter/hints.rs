@@ -0,0 +1,216 @@
+use std::ops::Bound;
+
+use compact_str::{CompactString, ToCompactString};
+use pandascore::endpoint::CollectionOptions;
+use trustfall::{
+    provider::{CandidateValue, Range, ResolveEdgeInfo, VertexInfo},
+    FieldValue,
+};
+
+/// Properties the API can filter leagues on, as `(property, API field)` pairs.
+pub(super) const LEAGUE_FILTERS: &[(&str, &str)] = &[("name", "name"), ("slug", "slug")];
+
+/// Properties the API can filter series on, as `(property, API field)` pairs.
+pub(super) const SERIES_FILTERS: &[(&str, &str)] = &[
+    ("name", "name"),
+    ("season", "season"),
+    ("slug", "slug"),
+    ("year", "year"),
+];
+
+/// Properties the API can filter tournaments on, as `(property, API field)` pairs.
+pub(super) const TOURNAMENT_FILTERS: &[(&str, &str)] = &[
+    ("detailed_stats", "detailed_stats"),
+    ("has_bracket", "has_bracket"),
+    ("live_supported", "live_supported"),
+    ("name", "name"),
+    ("slug", "slug"),
+    ("tier", "tier"),
+];
+
+/// Properties the API can filter matches on, as `(property, API field)` pairs.
+pub(super) const MATCH_FILTERS: &[(&str, &str)] = &[
+    ("detailed_stats", "detailed_stats"),
+    ("draw", "draw"),
+    ("forfeit", "forfeit"),
+    ("game_advantage", "game_advantage"),
+    ("match_type", "match_type"),
+    ("number_of_games", "number_of_games"),
+    ("match_status", "status"),
+    ("name", "name"),
+    ("slug", "slug"),
+];
+
+/// Properties the API can filter teams on, as `(property, API field)` pairs.
+pub(super) const TEAM_FILTERS: &[(&str, &str)] = &[
+    ("acronym", "acronym"),
+    ("location", "location"),
+    ("name", "name"),
+    ("slug", "slug"),
+];
+
+/// Properties the API can filter players on, as `(property, API field)` pairs.
+pub(super) const PLAYER_FILTERS: &[(&str, &str)] = &[
+    ("first_name", "first_name"),
+    ("last_name", "last_name"),
+    ("name", "name"),
+    ("nationality", "nationality"),
+    ("role", "role"),
+    ("slug", "slug"),
+];
+
+/// Properties that are `"unknown"` for values the client doesn't know, such as new match types.
+const UNKNOWN_VALUED: &[&str] = &["match_type", "match_status"];
+
+/// Returns the ids the query statically requires the vertex to have, if any.
+///
+/// `None` means the query places no usable constraint on `id`, while an empty list means no
+/// vertex can satisfy the query.
+pub(super) fn required_ids(info: &impl VertexInfo) -> Option<Vec<u64>> {
+    match info.statically_required_property("id")? {
+        CandidateValue::Impossible => Some(Vec::new()),
+        CandidateValue::Single(value) => Some(as_id(&value).into_iter().collect()),
+        CandidateValue::Multiple(values) => Some(values.iter().filter_map(as_id).collect()),
+        _ => None,
+    }
+}
+
+/// Builds the options for listing an edge's neighbors from the query's static filters on them.
+///
+/// Returns `None` if no neighbor can satisfy the query.
+pub(super) fn edge_options(
+    info: &ResolveEdgeInfo,
+    fields: &[(&str, &str)],
+    pinned: &[(&str, Vec<CompactString>)],
+) -> Option<CollectionOptions> {
+    let destination = info.destination();
+    let mut options = CollectionOptions::new();
+    if let Some(ids) = required_ids(&destination) {
+        if ids.is_empty() {
+            return None;
+        }
+        for id in ids {
+            options = options.filter("id", id.to_compact_string());
+        }
+    }
+    apply_filters(options, &destination, fields, pinned)
+}
+
+/// Narrows `options` with the query's static filters on `fields` that the API can evaluate.
+///
+/// Only values the query fixes, through `=` and `one_of`, are sent as filters, and integer
+/// bounds from `<`, `<=`, `>` and `>=` as ranges. Bounds on times, such as `begin_at`, and
+/// string operators, such as `has_prefix`, `has_substring` and `regex`, are not pushed down.
+/// Neither are the `"unknown"` values of [`UNKNOWN_VALUED`] properties.
+///
+/// Fields in `pinned` are already filtered on by parameters, and the API would OR the query's
+/// values with theirs, so they are only checked for values the parameters rule out.
+///
+/// Returns `None` if no vertex can satisfy the query.
+/// Trustfall still applies every filter locally, so a filter left out here only costs requests.
+pub(super) fn apply_filters(
+    mut options: CollectionOptions,
+    info: &impl VertexInfo,
+    fields: &[(&str, &str)],
+    pinned: &[(&str, Vec<CompactString>)],
+) -> Option<CollectionOptions> {
+    for &(property, field) in fields {
+        let candidate = info.statically_required_property(property);
+        if let Some((_, allowed)) = pinned.iter().find(|(pinned, _)| *pinned == field) {
+            if excludes(candidate.as_ref(), allowed) {
+                return None;
+            }
+            continue;
+        }
+        match candidate {
+            Some(CandidateValue::Impossible) => return None,
+            Some(CandidateValue::Single(value)) => {
+                if is_unknown(property, &value) {
+                    continue;
+                }
+                if let Some(value) = as_filter_value(&value) {
+                    options = options.filter(field, value);
+                }
+            }
+            Some(CandidateValue::Multiple(values)) => {
+                if values.iter().any(|value| is_unknown(property, value)) {
+                    continue;
+                }
+                let values = values
+                    .iter()
+                    .map(as_filter_value)
+                    .collect::<Option<Vec<_>>>();
+                for value in values.into_iter().flatten() {
+                    options = options.filter(field, value);
+                }
+            }
+            Some(CandidateValue::Range(range)) => {
+                if let Some((start, end)) = as_int_range(&range) {
+                    options = options.range(field, start, end);
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(options)
+}
+
+/// Whether `value` is the `"unknown"` value that `property` takes for values the client doesn't
+/// know, which the API has no name for.
+fn is_unknown(property: &str, value: &FieldValue) -> bool {
+    UNKNOWN_VALUED.contains(&property) && value.as_str() == Some("unknown")
+}
+
+/// Whether `candidate` can't take any of the `allowed` values.
+fn excludes(candidate: Option<&CandidateValue<FieldValue>>, allowed: &[CompactString]) -> bool {
+    let values = match candidate {
+        Some(CandidateValue::Impossible) => return true,
+        Some(CandidateValue::Single(value)) => std::slice::from_ref(value),
+        Some(CandidateValue::Multiple(values)) => values.as_slice(),
+        _ => return false,
+    };
+    values
+        .iter()
+        .map(as_filter_value)
+        .collect::<Option<Vec<_>>>()
+        .is_some_and(|values| !values.iter().any(|value| allowed.contains(value)))
+}
+
+fn as_id(value: &FieldValue) -> Option<u64> {
+    match value {
+        FieldValue::Int64(id) => u64::try_from(*id).ok(),
+        FieldValue::Uint64(id) => Some(*id),
+        _ => None,
+    }
+}
+
+fn as_filter_value(value: &FieldValue) -> Option<CompactString> {
+    match value {
+        // The API separates filter values with commas, so such values can't be expressed.
+        FieldValue::String(s) if s.contains(',') => None,
+        FieldValue::String(s) => Some(s.to_compact_string()),
+        FieldValue::Int64(i) => Some(i.to_compact_string()),
+        FieldValue::Uint64(i) => Some(i.to_compact_string()),
+        FieldValue::Boolean(b) => Some(b.to_compact_string()),
+        _ => None,
+    }
+}
+
+/// Converts an integer range to the inclusive bounds the API expects.
+///
+/// Ranges only come from comparison filters, which never match `null`, so the range's `null`
+/// flag can be ignored. Every integer property the API ranges over is non-negative, so an
+/// unbounded start becomes `0`.
+fn as_int_range(range: &Range<FieldValue>) -> Option<(i64, i64)> {
+    let start = match range.start_bound() {
+        Bound::Included(v) => v.as_i64()?,
+        Bound::Excluded(v) => v.as_i64()?.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(v) => v.as_i64()?,
+        Bound::Excluded(v) => v.as_i64()?.checked_sub(1)?,
+        Bound::Unbounded => i64::MAX,
+    };
+    Some((start, end))
+}
@@ -1,31 +1,124 @@
 mod adapter_impl;
+mod builder;
+pub(crate) mod cache;
 mod edges;
 mod entrypoints;
+mod hints;
+mod params;
 mod properties;
+mod query;
+mod retry;
 mod vertex;
 
 mod error;
 #[cfg(test)]
 mod tests;
 
-use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, MutexGuard, OnceLock},
+    task::{Context, Poll, Waker},
+};
 
-pub use error::AdapterError;
-use pandascore::{Client, ClientTransport};
-use tokio::runtime::Runtime;
-use trustfall::Schema;
+pub use builder::AdapterBuilder;
+pub use cache::CacheConfig;
+use cache::{Cached, VertexCache};
+use compact_str::ToCompactString;
+pub use error::{AdapterError, ErrorContext, ErrorPolicy};
+use pandascore::{
+    endpoint::{CollectionOptions, EndpointError},
+    Client, ClientTransport,
+};
+pub(crate) use query::QueryState;
+pub use query::{QueryRows, QueryStats, Row};
+pub(crate) use retry::parse_retry_after;
+pub use retry::{RetryAfterHeaders, RetryPolicy};
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+use trustfall::{FieldValue, Schema};
 pub use vertex::Vertex;
 
+use crate::rate_limit::RateLimiter;
+
 #[non_exhaustive]
 #[derive(Debug)]
 pub struct Adapter<T>(Arc<AdapterInner<T>>);
 
 #[derive(Debug)]
 pub struct AdapterInner<T> {
-    runtime: Runtime,
+    shared: Arc<Shared<T>>,
+    /// The errors of the query being executed, or of every query run on the adapter directly.
+    query: Arc<QueryState>,
+}
+
+/// The client, cache and settings that every query runs with.
+#[derive(Debug)]
+struct Shared<T> {
+    runtime: Executor,
     client: Client<T>,
+    cache: VertexCache,
+    /// How many pages paginated endpoints fetch beyond the one being consumed.
+    read_ahead: usize,
+    rate_limiter: Option<RateLimiter>,
+    retry: RetryPolicy,
+    error_policy: ErrorPolicy,
+}
 
-    errors: Mutex<Vec<AdapterError>>,
+/// The runtime requests are executed on.
+#[derive(Debug)]
+enum Executor {
+    /// A runtime owned by the adapter, only taken when the adapter is dropped.
+    Owned(Option<Runtime>),
+    /// A runtime owned by the application embedding the adapter.
+    Handle(Handle),
+}
+
+impl Executor {
+    /// Blocks on `future` until it completes.
+    ///
+    /// Fails when called from inside a current-thread runtime, whose only thread can neither
+    /// block nor be handed off.
+    fn block_on<F: Future>(&self, future: F) -> Result<F::Output, AdapterError> {
+        match self {
+            Self::Owned(runtime) => Ok(runtime
+                .as_ref()
+                .expect("runtime used after drop")
+                .block_on(future)),
+            Self::Handle(handle) => match Handle::try_current() {
+                Ok(current) if current.runtime_flavor() == RuntimeFlavor::CurrentThread => {
+                    Err(AdapterError::CurrentThreadRuntime)
+                }
+                // Blocking a runtime thread would stall its other tasks, so hand its tasks off
+                // first.
+                Ok(_) => Ok(tokio::task::block_in_place(|| handle.block_on(future))),
+                Err(_) => Ok(handle.block_on(future)),
+            },
+        }
+    }
+
+    /// Polls `future` once within the runtime, waking nothing when it is ready.
+    ///
+    /// Requests the future has sent keep progressing on the runtime's workers in between.
+    fn poll<F: Future + Unpin>(&self, future: &mut F) -> Poll<F::Output> {
+        let _guard = match self {
+            Self::Owned(runtime) => runtime.as_ref().expect("runtime used after drop").enter(),
+            Self::Handle(handle) => handle.enter(),
+        };
+        Pin::new(future).poll(&mut Context::from_waker(Waker::noop()))
+    }
+}
+
+impl Drop for Executor {
+    fn drop(&mut self) {
+        // Dropping a runtime waits for its tasks, which panics when done from async code.
+        // Requests only run inside `block_on`, so there is nothing left to wait for.
+        if let Self::Owned(runtime) = self {
+            if let Some(runtime) = runtime.take() {
+                runtime.shutdown_background();
+            }
+        }
+    }
 }
 
 static SCHEMA: OnceLock<Schema> = OnceLock::new();
@@ -38,45 +131,276 @@ impl<T: ClientTransport> Adapter<T> {
     }
 
     pub fn new(client: Client<T>) -> Self {
+        Self::builder(client).build()
+    }
+
+    /// Creates an adapter that executes requests on an existing runtime, such as the one an
+    /// async service runs on.
+    ///
+    /// See [`AdapterBuilder::handle`].
+    pub fn with_handle(client: Client<T>, handle: Handle) -> Self {
+        Self::builder(client).handle(handle).build()
+    }
+
+    pub fn builder(client: Client<T>) -> AdapterBuilder<T> {
+        AdapterBuilder::new(client)
+    }
+
+    /// The errors of queries run with [`trustfall::execute_query`] on this adapter.
+    ///
+    /// Under [`ErrorPolicy::FailFast`], the error that stopped a query is recorded here too, and
+    /// the rows the query produced after it may be missing data.
+    ///
+    /// Queries run with [`execute_query`](Self::execute_query) keep their errors apart, in their
+    /// [`QueryRows`].
+    pub fn errors(&self) -> MutexGuard<'_, Vec<AdapterError>> {
+        self.0.errors()
+    }
+
+    /// The work done for queries run with [`trustfall::execute_query`] on this adapter.
+    pub fn stats(&self) -> QueryStats {
+        self.0.query.stats()
+    }
+
+    /// Creates an adapter that shares this one's client, runtime, cache and settings, but keeps
+    /// its own [`errors`](Self::errors) and [`stats`](Self::stats).
+    ///
+    /// Useful to tell apart queries run with [`trustfall::execute_query`].
+    pub fn session(&self) -> Self {
+        Self::with_state(&self.0.shared, Arc::default())
+    }
+
+    fn with_state(shared: &Arc<Shared<T>>, query: Arc<QueryState>) -> Self {
         Self(Arc::new(AdapterInner {
-            runtime: tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-                .unwrap(),
-            client,
-            errors: Mutex::new(Vec::new()),
+            shared: Arc::clone(shared),
+            query,
         }))
     }
 
-    pub fn errors(&self) -> MutexGuard<Vec<AdapterError>> {
-        self.0.errors()
+    /// Executes `query` with `args`, yielding its rows.
+    ///
+    /// Under [`ErrorPolicy::FailFast`], the first error ends the rows with that error.
+    /// Other errors are recorded for [`QueryRows::take_errors`].
+    pub fn execute_query<'a>(
+        &self,
+        query: &str,
+        args: BTreeMap<impl Into<Arc<str>>, impl Into<FieldValue>>,
+    ) -> Result<QueryRows<'a>, AdapterError>
+    where
+        T: 'a,
+    {
+        self.execute_query_with(Arc::new(QueryState::for_rows()), query, args)
+    }
+
+    /// Executes `query` with `args`, recording its errors in `state`.
+    pub(crate) fn execute_query_with<'a>(
+        &self,
+        state: Arc<QueryState>,
+        query: &str,
+        args: BTreeMap<impl Into<Arc<str>>, impl Into<FieldValue>>,
+    ) -> Result<QueryRows<'a>, AdapterError>
+    where
+        T: 'a,
+    {
+        let adapter = Arc::new(Self::with_state(&self.0.shared, Arc::clone(&state)));
+        let rows = trustfall::execute_query(Self::schema(), adapter, query, args)
+            .map_err(|e| AdapterError::InvalidQuery(format!("{e:#}")))?;
+        Ok(QueryRows::new(rows, state))
     }
 }
 
 impl<T: ClientTransport> AdapterInner<T> {
-    pub fn errors(&self) -> MutexGuard<Vec<AdapterError>> {
-        self.errors.lock().unwrap()
+    pub fn errors(&self) -> MutexGuard<'_, Vec<AdapterError>> {
+        self.query.errors()
+    }
+
+    /// Handles `error` as the error policy says.
+    pub(super) fn report(&self, error: AdapterError) {
+        self.query.report(self.shared.error_policy, error);
     }
 
-    pub(super) fn execute<R>(&self, request: R) -> Option<R::Response>
+    /// Executes `request`, recording a failure with `context`.
+    pub(super) fn execute<R>(&self, request: R, context: ErrorContext) -> Option<R::Response>
     where
-        R: pandascore::endpoint::Endpoint + std::fmt::Debug,
+        R: pandascore::endpoint::Endpoint + Clone + std::fmt::Debug,
     {
+        if self.query.is_stopped() {
+            return None;
+        }
         #[cfg(feature = "log")]
         {
             log::debug!("Executing request: {request:?}");
         }
 
-        let res = self.runtime.block_on(self.client.execute(request));
+        let res = self.block_on(self.send(request)).flatten()?;
+        self.record::<R, _>(res, context)
+    }
+
+    /// Sends `requests` concurrently, resolving to their responses in order.
+    ///
+    /// The requests can be driven with [`poll`](Self::poll) while other work is done, and then
+    /// waited for with [`block_on`](Self::block_on).
+    pub(crate) async fn send_all<R>(
+        &self,
+        requests: Vec<(R, ErrorContext)>,
+    ) -> Vec<Option<R::Response>>
+    where
+        R: pandascore::endpoint::Endpoint + Clone + std::fmt::Debug,
+    {
+        if self.query.is_stopped() {
+            return requests.iter().map(|_| None).collect();
+        }
+        #[cfg(feature = "log")]
+        {
+            log::debug!("Executing requests: {requests:?}");
+        }
+
+        let (requests, contexts): (Vec<_>, Vec<_>) = requests.into_iter().unzip();
+        let futures = requests.into_iter().map(|r| self.send(r));
+        let results = futures_util::future::join_all(futures).await;
+        results
+            .into_iter()
+            .zip(contexts)
+            .map(|(res, context)| self.record::<R, _>(res?, context))
+            .collect()
+    }
+
+    /// Polls `future` once on the adapter's runtime without blocking.
+    pub(crate) fn poll<F: Future + Unpin>(&self, future: &mut F) -> Poll<F::Output> {
+        self.shared.runtime.poll(future)
+    }
+
+    /// Blocks on `future` on the adapter's runtime.
+    ///
+    /// Returns `None` and records an error if the runtime can't be blocked on from here.
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> Option<F::Output> {
+        self.shared
+            .runtime
+            .block_on(future)
+            .map_err(|e| self.report(e))
+            .ok()
+    }
+
+    /// Executes `request` once the rate limiter allows it, retrying it as the retry policy says.
+    ///
+    /// Returns `None` if the query is stopped before the request is sent.
+    async fn send<R>(&self, request: R) -> Option<Result<R::Response, EndpointError>>
+    where
+        R: pandascore::endpoint::Endpoint + Clone + std::fmt::Debug,
+    {
+        let mut attempt = 1;
+        loop {
+            if let Some(limiter) = &self.shared.rate_limiter {
+                limiter.acquire().await;
+            }
+
+            // Waiting for the rate limiter or a retry can outlast the query.
+            if self.query.is_stopped() {
+                return None;
+            }
+            self.query.count_request();
+            let res = self.shared.client.execute(request.clone()).await;
+            let Some(delay) = res
+                .as_ref()
+                .err()
+                .and_then(|e| self.shared.retry.delay(attempt, e))
+            else {
+                return Some(res);
+            };
+
+            #[cfg(feature = "log")]
+            {
+                if let Err(e) = &res {
+                    log::warn!("Retrying {request:?} in {delay:?} after attempt {attempt}: {e}");
+                }
+            }
+            self.query.count_retry();
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    pub(crate) fn read_ahead(&self) -> usize {
+        self.shared.read_ahead
+    }
+
+    /// Records the error of a failed request to endpoint `R`, adding its path to `context`.
+    fn record<R, O>(&self, res: Result<O, EndpointError>, mut context: ErrorContext) -> Option<O> {
         match res {
             Ok(r) => Some(r),
-            Err(e) => {
-                self.errors
-                    .lock()
-                    .unwrap()
-                    .push(AdapterError::EndpointError(e));
+            Err(source) => {
+                self.query.count_failed_request();
+                context.endpoint = Some(match &source {
+                    EndpointError::Reqwest(e) if e.url().is_some() => {
+                        e.url().map(|url| url.path().to_owned()).unwrap_or_default()
+                    }
+                    _ => std::any::type_name::<R>().to_owned(),
+                });
+                self.report(AdapterError::EndpointError { source, context });
                 None
             }
         }
     }
+
+    /// Fetches the entity with `id`, answering from the cache when possible.
+    pub(super) fn fetch<E: Cached>(&self, id: u64, context: ErrorContext) -> Option<E> {
+        let cached = self.shared.cache.get(id);
+        self.query.count_cache_lookup(cached.is_some());
+        if cached.is_some() {
+            return cached;
+        }
+        let entity = self.execute(E::Get::from(id), context)?;
+        self.shared.cache.insert(&entity);
+        Some(entity)
+    }
+
+    /// Fetches the entities with `ids` in as few list requests as possible, answering from the
+    /// cache when it can.
+    ///
+    /// Entities the API doesn't return are missing from the result.
+    pub(super) fn fetch_many<E: Cached>(
+        &self,
+        ids: &[u64],
+        context: &ErrorContext,
+    ) -> HashMap<u64, E> {
+        let mut found = HashMap::new();
+        let mut missing = Vec::new();
+        let mut seen = HashSet::new();
+        for &id in ids {
+            if !seen.insert(id) {
+                continue;
+            }
+            let cached = self.shared.cache.get(id);
+            self.query.count_cache_lookup(cached.is_some());
+            match cached {
+                Some(entity) => {
+                    found.insert(id, entity);
+                }
+                None => missing.push(id),
+            }
+        }
+
+        for chunk in missing.chunks(params::MAX_PAGE_SIZE) {
+            // Lossless, since chunks are at most `MAX_PAGE_SIZE` long.
+            let options = chunk.iter().fold(
+                CollectionOptions::new().per_page(chunk.len() as u32),
+                |options, id| options.filter("id", id.to_compact_string()),
+            );
+            let Some(response) = self.execute(E::list(options), context.clone()) else {
+                continue;
+            };
+            for entity in response.results {
+                self.shared.cache.insert(&entity);
+                found.insert(entity.id(), entity);
+            }
+        }
+        found
+    }
+
+    /// Caches entities returned by a list endpoint, so later fetches can skip the request.
+    pub(crate) fn remember<E: Cached>(&self, entities: &[E]) {
+        for entity in entities {
+            self.shared.cache.insert(entity);
+        }
+    }
 }
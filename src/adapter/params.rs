@@ -0,0 +1,465 @@
+use compact_str::{format_compact, CompactString, ToCompactString};
+use pandascore::{endpoint::CollectionOptions, ClientTransport};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use trustfall::{provider::EdgeParameters, FieldValue};
+
+use crate::{
+    adapter::{AdapterError, AdapterInner, Vertex},
+    field_value::IntoFieldValue,
+};
+
+/// The largest page size the API accepts.
+pub(super) const MAX_PAGE_SIZE: usize = 100;
+
+/// Optional parameters that order and narrow a collection edge.
+#[derive(Debug, Clone, Default)]
+pub(super) struct CollectionParams {
+    sort: Option<CompactString>,
+    limit: Option<usize>,
+    since: Option<OffsetDateTime>,
+    until: Option<OffsetDateTime>,
+    status: Option<CompactString>,
+    /// The timestamp `since` and `until` are checked against.
+    time: TimeField,
+    /// Where series or tournaments must be in their schedule, as of the given time.
+    progress: Option<(Progress, OffsetDateTime)>,
+}
+
+/// Where a series or tournament is in its schedule, for the running/upcoming/past edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Progress {
+    Running,
+    Upcoming,
+    Past,
+}
+
+/// The timestamp of a vertex that a `since`/`until` window is checked against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum TimeField {
+    #[default]
+    BeginAt,
+    ModifiedAt,
+}
+
+impl TimeField {
+    fn name(self) -> &'static str {
+        match self {
+            Self::BeginAt => "begin_at",
+            Self::ModifiedAt => "modified_at",
+        }
+    }
+
+    fn of(self, vertex: &Vertex) -> Option<OffsetDateTime> {
+        match (self, vertex) {
+            (Self::BeginAt, Vertex::Series(x)) => x.begin_at,
+            (Self::BeginAt, Vertex::Tournament(x)) => x.begin_at,
+            (Self::BeginAt, Vertex::Match(x)) => x.begin_at,
+            (Self::ModifiedAt, Vertex::League(x)) => Some(x.modified_at),
+            (Self::ModifiedAt, Vertex::Series(x)) => Some(x.modified_at),
+            (Self::ModifiedAt, Vertex::Tournament(x)) => Some(x.modified_at),
+            (Self::ModifiedAt, Vertex::Match(x)) => Some(x.modified_at),
+            (Self::ModifiedAt, Vertex::Team(x)) => Some(x.modified_at),
+            (Self::ModifiedAt, Vertex::Player(x)) => Some(x.modified_at),
+            _ => None,
+        }
+    }
+}
+
+/// The order a collection is listed in, by its [`TimeField`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Order {
+    Ascending,
+    Descending,
+}
+
+impl CollectionParams {
+    /// Reads the collection parameters out of `parameters`, with `since` and `until` checked
+    /// against `time`.
+    ///
+    /// Returns `None` and records an error if a parameter has an invalid value.
+    pub(super) fn parse<T: ClientTransport>(
+        adapter: &AdapterInner<T>,
+        parameters: &EdgeParameters,
+        time: TimeField,
+    ) -> Option<Self> {
+        Self::try_parse(parameters, time)
+            .map_err(|e| adapter.report(e))
+            .ok()
+    }
+
+    fn try_parse(parameters: &EdgeParameters, time: TimeField) -> Result<Self, AdapterError> {
+        let limit = parameters
+            .get("limit")
+            .filter(|value| !matches!(value, FieldValue::Null))
+            .map(|value| {
+                value
+                    .as_u64()
+                    .and_then(|limit| usize::try_from(limit).ok())
+                    .ok_or_else(|| invalid("limit", value))
+            })
+            .transpose()?;
+        Ok(Self {
+            sort: parameters
+                .get("sort")
+                .and_then(FieldValue::as_str)
+                .map(Into::into),
+            limit,
+            since: parse_time(parameters, "since")?,
+            until: parse_time(parameters, "until")?,
+            status: parameters
+                .get("status")
+                .and_then(FieldValue::as_str)
+                .map(Into::into),
+            time,
+            progress: None,
+        })
+    }
+
+    /// Adds the parameters the API can evaluate to `options`.
+    ///
+    /// The API can't filter on timestamps, so without a `sort` a `since`/`until` window lists
+    /// the collection by its timestamp, for [`restrict`](Self::restrict) to stop paging once
+    /// the window is passed.
+    pub(super) fn apply(&self, mut options: CollectionOptions) -> CollectionOptions {
+        if let Some(sort) = &self.sort {
+            options = options.sort(sort.clone());
+        } else if let Some(order) = self.order() {
+            let field = self.time.name();
+            options = options.sort(match order {
+                Order::Ascending => field.to_compact_string(),
+                Order::Descending => format_compact!("-{field}"),
+            });
+        } else if let Some((progress, _)) = self.progress {
+            // List the ones most likely to be in progress, or most recently ended, first.
+            options = options.sort(match progress {
+                Progress::Running | Progress::Upcoming => "-begin_at",
+                Progress::Past => "-end_at",
+            });
+        }
+        if let Some(status) = &self.status {
+            options = options.filter("status", status.clone());
+        }
+        if let Some(limit) = self.limit {
+            // Lossless, since the page size is clamped well below `u32::MAX`.
+            options = options.per_page(limit.clamp(1, MAX_PAGE_SIZE) as u32);
+        }
+        options
+    }
+
+    /// The API filters that the parameters fix, as `(API field, values)` pairs.
+    ///
+    /// Filters the query pushes down on these fields must not be merged into the parameters',
+    /// since the API ORs the values of a filter together.
+    pub(super) fn pinned(&self) -> Vec<(&'static str, Vec<CompactString>)> {
+        self.status
+            .iter()
+            .map(|status| ("status", split(status)))
+            .collect()
+    }
+
+    /// Checks the parameters against `items` locally and applies `limit`.
+    ///
+    /// Filters sent to the API are checked again, since a pushed-down filter may widen them.
+    /// Items without the timestamp are dropped whenever a `since`/`until` window is set.
+    pub(super) fn restrict<'a>(
+        &self,
+        items: impl Iterator<Item = Vertex> + 'a,
+    ) -> impl Iterator<Item = Vertex> + 'a {
+        let (params, order) = (self.clone(), self.order());
+        let limit = self.limit.unwrap_or(usize::MAX);
+        items
+            .take_while({
+                let params = params.clone();
+                move |vertex| !params.passed(order, vertex)
+            })
+            .filter(move |vertex| params.admits(vertex))
+            .take(limit)
+    }
+
+    /// The order the collection is listed in by its timestamp, if any.
+    fn order(&self) -> Option<Order> {
+        if self.since.is_none() && self.until.is_none() {
+            return None;
+        }
+        let Some(sort) = &self.sort else {
+            // Newest first reaches a `since` bound soonest, and oldest first an `until` bound.
+            return Some(if self.since.is_some() {
+                Order::Descending
+            } else {
+                Order::Ascending
+            });
+        };
+        let field = self.time.name();
+        let first = sort.split(',').next()?;
+        match first.strip_prefix('-') {
+            Some(key) => (key == field).then_some(Order::Descending),
+            None => (first == field).then_some(Order::Ascending),
+        }
+    }
+
+    /// Whether `vertex`, listed in `order`, is past the window, so that no later one is in it.
+    fn passed(&self, order: Option<Order>, vertex: &Vertex) -> bool {
+        let Some(time) = self.time.of(vertex) else {
+            return false;
+        };
+        match order {
+            Some(Order::Descending) => self.since.is_some_and(|since| time < since),
+            Some(Order::Ascending) => self.until.is_some_and(|until| time >= until),
+            None => false,
+        }
+    }
+
+    /// Whether `vertex` satisfies the parameters.
+    fn admits(&self, vertex: &Vertex) -> bool {
+        if self.since.is_some() || self.until.is_some() {
+            let in_window = self.time.of(vertex).is_some_and(|time| {
+                self.since.is_none_or(|since| time >= since)
+                    && self.until.is_none_or(|until| time < until)
+            });
+            if !in_window {
+                return false;
+            }
+        }
+        if let Some(status) = &self.status {
+            let Vertex::Match(m) = vertex else {
+                return false;
+            };
+            let FieldValue::String(actual) = m.status.into_field_value() else {
+                return false;
+            };
+            if !split(status).iter().any(|s| **s == *actual) {
+                return false;
+            }
+        }
+        if let Some((progress, now)) = self.progress {
+            let (begin_at, end_at) = match vertex {
+                Vertex::Series(x) => (x.begin_at, x.end_at),
+                Vertex::Tournament(x) => (x.begin_at, x.end_at),
+                _ => return false,
+            };
+            let in_progress = match progress {
+                Progress::Running => {
+                    begin_at.is_some_and(|begin_at| begin_at <= now)
+                        && end_at.is_none_or(|end_at| end_at > now)
+                }
+                Progress::Upcoming => begin_at.is_some_and(|begin_at| begin_at > now),
+                Progress::Past => end_at.is_some_and(|end_at| end_at <= now),
+            };
+            if !in_progress {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Parameters of the root entrypoints.
+#[derive(Debug, Clone, Default)]
+pub(super) struct EntrypointParams {
+    game: Option<CompactString>,
+    video_game: Option<u64>,
+    search: Option<CompactString>,
+    search_field: Option<CompactString>,
+    slug: Option<CompactString>,
+    ids: Option<Vec<u64>>,
+    page_size: Option<usize>,
+    collection: CollectionParams,
+}
+
+impl EntrypointParams {
+    /// Reads the entrypoint parameters out of `parameters`, with `since` and `until` checked
+    /// against `time`.
+    ///
+    /// Returns `None` and records an error if a parameter has an invalid value.
+    pub(super) fn parse<T: ClientTransport>(
+        adapter: &AdapterInner<T>,
+        parameters: &EdgeParameters,
+        time: TimeField,
+    ) -> Option<Self> {
+        Self::try_parse(parameters, time)
+            .map_err(|e| adapter.report(e))
+            .ok()
+    }
+
+    fn try_parse(parameters: &EdgeParameters, time: TimeField) -> Result<Self, AdapterError> {
+        let str_param = |name| {
+            parameters
+                .get(name)
+                .and_then(FieldValue::as_str)
+                .map(Into::into)
+        };
+        let ids = parameters
+            .get("ids")
+            .and_then(FieldValue::as_slice)
+            .map(|ids| {
+                ids.iter()
+                    .map(|id| id.as_u64().ok_or_else(|| invalid("ids", id)))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+        let page_size = parameters
+            .get("page_size")
+            .filter(|value| !matches!(value, FieldValue::Null))
+            .map(|value| {
+                value
+                    .as_u64()
+                    .and_then(|size| usize::try_from(size).ok())
+                    .filter(|size| (1..=MAX_PAGE_SIZE).contains(size))
+                    .ok_or_else(|| invalid("page_size", value))
+            })
+            .transpose()?;
+        Ok(Self {
+            game: str_param("game"),
+            video_game: None,
+            search: str_param("search"),
+            search_field: str_param("search_field"),
+            slug: str_param("slug"),
+            ids,
+            page_size,
+            collection: CollectionParams::try_parse(parameters, time)?,
+        })
+    }
+
+    /// Parameters that select every vertex of the video game with id `video_game`.
+    pub(super) fn for_video_game(video_game: u64) -> Self {
+        Self {
+            video_game: Some(video_game),
+            ..Self::default()
+        }
+    }
+
+    /// Restricts the matches to those with `status`, such as `running`, for the
+    /// running/upcoming/past edges.
+    ///
+    /// The status is sent as a `status` filter on the generic list endpoint, since the client's
+    /// status endpoints drop every other option, including the page number.
+    pub(super) fn with_status(mut self, status: &'static str) -> Self {
+        self.collection.status = Some(status.into());
+        self
+    }
+
+    /// Restricts the series or tournaments to those at `progress` in their schedule, for the
+    /// running/upcoming/past edges.
+    ///
+    /// The API has no status filter for them, so the generic list is checked locally. Upcoming
+    /// ones are listed newest first from now on, so that paging stops at the first one begun.
+    pub(super) fn with_progress(mut self, progress: Progress) -> Self {
+        let now = OffsetDateTime::now_utc();
+        if progress == Progress::Upcoming {
+            self.collection.since = Some(self.collection.since.map_or(now, |since| since.max(now)));
+        }
+        self.collection.progress = Some((progress, now));
+        self
+    }
+
+    pub(super) fn game(&self) -> Option<&str> {
+        self.game.as_deref()
+    }
+
+    pub(super) fn video_game(&self) -> Option<u64> {
+        self.video_game
+    }
+
+    /// Whether the parameters narrow the collection in ways that are only checked by the API,
+    /// which direct gets can't do.
+    pub(super) fn narrows(&self) -> bool {
+        self.game.is_some() || self.video_game.is_some() || self.search.is_some()
+    }
+
+    /// Combines the `ids` parameter with the ids the query statically requires.
+    ///
+    /// `None` means neither constrains the ids, while an empty list means no vertex can match.
+    pub(super) fn ids(&self, required: Option<Vec<u64>>) -> Option<Vec<u64>> {
+        match (&self.ids, required) {
+            (Some(ids), Some(required)) => Some(
+                ids.iter()
+                    .copied()
+                    .filter(|id| required.contains(id))
+                    .collect(),
+            ),
+            (ids, required) => ids.clone().or(required),
+        }
+    }
+
+    /// Builds the options for listing the collection, restricted to `ids` if given.
+    pub(super) fn options(&self, ids: Option<Vec<u64>>) -> CollectionOptions {
+        let mut options = CollectionOptions::new();
+        if let Some(search) = &self.search {
+            let field = self.search_field.clone().unwrap_or_else(|| "name".into());
+            options = options.search(field, search.clone());
+        }
+        if let Some(slug) = &self.slug {
+            options = options.filter("slug", slug.clone());
+        }
+        for id in ids.into_iter().flatten() {
+            options = options.filter("id", id.to_compact_string());
+        }
+        if let Some(page_size) = self.page_size {
+            // Lossless, since the page size is at most `MAX_PAGE_SIZE`.
+            options = options.per_page(page_size as u32);
+        }
+        self.collection.apply(options)
+    }
+
+    /// The API filters that the parameters fix, as `(API field, values)` pairs.
+    pub(super) fn pinned(&self) -> Vec<(&'static str, Vec<CompactString>)> {
+        let mut pinned = self.collection.pinned();
+        if let Some(slug) = &self.slug {
+            pinned.push(("slug", split(slug)));
+        }
+        pinned
+    }
+
+    /// Checks the parameters against `items` locally, since a pushed-down filter may widen
+    /// the ones sent to the API.
+    pub(super) fn restrict<'a>(
+        &self,
+        items: impl Iterator<Item = Vertex> + 'a,
+    ) -> impl Iterator<Item = Vertex> + 'a {
+        let slugs = self.slug.as_deref().map(split);
+        let items = items.filter(move |vertex| {
+            slugs.as_ref().is_none_or(|slugs| {
+                slug(vertex).is_some_and(|slug| slugs.iter().any(|s| s == slug))
+            })
+        });
+        self.collection.restrict(items)
+    }
+}
+
+fn parse_time(
+    parameters: &EdgeParameters,
+    name: &'static str,
+) -> Result<Option<OffsetDateTime>, AdapterError> {
+    let Some(value) = parameters
+        .get(name)
+        .filter(|value| !matches!(value, FieldValue::Null))
+    else {
+        return Ok(None);
+    };
+    value
+        .as_str()
+        .and_then(|s| OffsetDateTime::parse(s, &Rfc3339).ok())
+        .map(Some)
+        .ok_or_else(|| invalid(name, value))
+}
+
+fn slug(vertex: &Vertex) -> Option<&str> {
+    match vertex {
+        Vertex::League(x) => Some(&x.slug),
+        Vertex::Series(x) => Some(&x.slug),
+        Vertex::Tournament(x) => Some(&x.slug),
+        Vertex::Match(x) => Some(&x.slug),
+        Vertex::Team(x) => x.slug.as_deref(),
+        Vertex::Player(x) => x.slug.as_deref(),
+        _ => None,
+    }
+}
+
+/// Splits a filter value into the values the API ORs together.
+fn split(value: &str) -> Vec<CompactString> {
+    value.split(',').map(CompactString::from).collect()
+}
+
+fn invalid(name: &'static str, value: &FieldValue) -> AdapterError {
+    AdapterError::InvalidParameter(name, format!("{value:?}"))
+}
@@ -1,14 +1,23 @@
-use pandascore::model::Winner;
+use std::sync::Arc;
+
+use pandascore::{
+    model::{matches::MatchResult, Winner},
+    ClientTransport,
+};
 use trustfall::{
     provider::{AsVertex, ContextIterator, ContextOutcomeIterator},
     FieldValue,
 };
 
 use super::vertex::Vertex;
-use crate::field_value::IntoFieldValue;
+use crate::{
+    adapter::{AdapterError, AdapterInner},
+    field_value::IntoFieldValue,
+};
 
 macro_rules! impl_properties {
     (
+        $adapter:ident,
         $contexts:ident,
         $prop_name:ident,
         $ty:ident,
@@ -16,17 +25,18 @@ macro_rules! impl_properties {
     ) => {
         match $prop_name {
             $(
-                stringify!($f) => Box::new($contexts.map(|ctx| {
+                stringify!($f) => Box::new($contexts.map(move |ctx| {
                     let value = ctx.active_vertex().map(|v| match v {
                         Vertex::$ty(v) => impl_properties!(@internal v, $f $(=> $f2)?),
-                        _ => unreachable!("expected active vertex to be '{}'", stringify!($ty)),
+                        _ => {
+                            $adapter.report(v.mismatch(stringify!($ty)));
+                            FieldValue::Null
+                        }
                     });
                     (ctx, value.into())
                 })),
             )*
-            _ => {
-                unreachable!("attempted to read unexpected property '{}' on type '{}'", $prop_name, stringify!($ty))
-            }
+            _ => unknown_property(&$adapter, $contexts, stringify!($ty), $prop_name),
         }
     };
 
@@ -39,10 +49,12 @@ macro_rules! impl_properties {
 }
 
 pub(super) fn resolve_league_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     impl_properties! {
+        adapter,
         contexts,
         property_name,
         League,
@@ -56,10 +68,12 @@ pub(super) fn resolve_league_property<'a, V: AsVertex<Vertex> + 'a>(
 }
 
 pub(super) fn resolve_series_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     impl_properties! {
+        adapter,
         contexts,
         property_name,
         Series,
@@ -76,10 +90,12 @@ pub(super) fn resolve_series_property<'a, V: AsVertex<Vertex> + 'a>(
 }
 
 pub(super) fn resolve_tournament_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     impl_properties! {
+        adapter,
         contexts,
         property_name,
         Tournament,
@@ -98,10 +114,12 @@ pub(super) fn resolve_tournament_property<'a, V: AsVertex<Vertex> + 'a>(
 }
 
 pub(super) fn resolve_match_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     impl_properties! {
+        adapter,
         contexts,
         property_name,
         Match,
@@ -127,11 +145,50 @@ pub(super) fn resolve_match_property<'a, V: AsVertex<Vertex> + 'a>(
     }
 }
 
+pub(super) fn resolve_match_result_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "opponent_id" => Box::new(contexts.map(move |ctx| {
+            let value = ctx.active_vertex().map(|v| match v {
+                Vertex::MatchResult(MatchResult::Team { team_id, .. }) => {
+                    team_id.into_field_value()
+                }
+                Vertex::MatchResult(MatchResult::Player { player_id, .. }) => {
+                    player_id.into_field_value()
+                }
+                _ => {
+                    adapter.report(v.mismatch("MatchResult"));
+                    FieldValue::Null
+                }
+            });
+            (ctx, value.into())
+        })),
+        "score" => Box::new(contexts.map(move |ctx| {
+            let value = ctx.active_vertex().map(|v| match v {
+                Vertex::MatchResult(
+                    MatchResult::Team { score, .. } | MatchResult::Player { score, .. },
+                ) => score.into_field_value(),
+                _ => {
+                    adapter.report(v.mismatch("MatchResult"));
+                    FieldValue::Null
+                }
+            });
+            (ctx, value.into())
+        })),
+        _ => unknown_property(&adapter, contexts, "MatchResult", property_name),
+    }
+}
+
 pub(super) fn resolve_team_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     impl_properties! {
+        adapter,
         contexts,
         property_name,
         Team,
@@ -146,10 +203,12 @@ pub(super) fn resolve_team_property<'a, V: AsVertex<Vertex> + 'a>(
 }
 
 pub(super) fn resolve_player_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     impl_properties! {
+        adapter,
         contexts,
         property_name,
         Player,
@@ -168,10 +227,12 @@ pub(super) fn resolve_player_property<'a, V: AsVertex<Vertex> + 'a>(
 }
 
 pub(super) fn resolve_video_game_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     impl_properties! {
+        adapter,
         contexts,
         property_name,
         VideoGame,
@@ -183,69 +244,134 @@ pub(super) fn resolve_video_game_property<'a, V: AsVertex<Vertex> + 'a>(
 }
 
 pub(super) fn resolve_winner_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "id" => Box::new(contexts.map(|ctx| {
+        "id" => Box::new(contexts.map(move |ctx| {
             let value = ctx.active_vertex().map(|v| match v {
                 Vertex::Winner(v) => match v {
                     Winner::Team { id, .. } => id.into_field_value(),
                     Winner::Player { id, .. } => id.into_field_value(),
-                    _ => unreachable!("unexpected winner variant"),
+                    // Winners of other kinds have no id the schema knows of.
+                    _ => FieldValue::Null,
                 },
-                _ => unreachable!("expected active vertex to be '{}'", stringify!(Winner)),
+                _ => {
+                    adapter.report(v.mismatch("Winner"));
+                    FieldValue::Null
+                }
             });
             (ctx, value.into())
         })),
-        _ => {
-            unreachable!(
-                "attempted to read unexpected property '{}' on type '{}'",
-                property_name,
-                stringify!(Winner)
-            )
-        }
+        _ => unknown_property(&adapter, contexts, "Winner", property_name),
     }
 }
 
 pub(super) fn resolve_winner_team_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "id" => Box::new(contexts.map(|ctx| {
+        "id" => Box::new(contexts.map(move |ctx| {
             let value = ctx
                 .active_vertex()
                 .and_then(|v| v.as_winner_team().map(|(id, _)| id.into_field_value()));
             (ctx, value.into())
         })),
-        _ => {
-            unreachable!(
-                "attempted to read unexpected property '{}' on type '{}'",
-                property_name,
-                stringify!(Winner)
-            )
-        }
+        _ => unknown_property(&adapter, contexts, "WinnerTeam", property_name),
     }
 }
 
 pub(super) fn resolve_winner_player_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
     contexts: ContextIterator<'a, V>,
     property_name: &str,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "id" => Box::new(contexts.map(|ctx| {
+        "id" => Box::new(contexts.map(move |ctx| {
             let value = ctx
                 .active_vertex()
                 .and_then(|v| v.as_winner_player().map(|(id, _)| id.into_field_value()));
             (ctx, value.into())
         })),
-        _ => {
-            unreachable!(
-                "attempted to read unexpected property '{}' on type '{}'",
-                property_name,
-                stringify!(Winner)
-            )
-        }
+        _ => unknown_property(&adapter, contexts, "WinnerPlayer", property_name),
     }
 }
+
+pub(super) fn resolve_opponent_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "id" => Box::new(contexts.map(move |ctx| {
+            let value = ctx.active_vertex().map(|v| match v {
+                Vertex::OpponentTeam(team) => team.id.into_field_value(),
+                Vertex::OpponentPlayer(player) => player.id.into_field_value(),
+                _ => {
+                    adapter.report(v.mismatch("Opponent"));
+                    FieldValue::Null
+                }
+            });
+            (ctx, value.into())
+        })),
+        "name" => Box::new(contexts.map(move |ctx| {
+            let value = ctx.active_vertex().map(|v| match v {
+                Vertex::OpponentTeam(team) => team.name.clone().into_field_value(),
+                Vertex::OpponentPlayer(player) => player.name.clone().into_field_value(),
+                _ => {
+                    adapter.report(v.mismatch("Opponent"));
+                    FieldValue::Null
+                }
+            });
+            (ctx, value.into())
+        })),
+        _ => unknown_property(&adapter, contexts, "Opponent", property_name),
+    }
+}
+
+pub(super) fn resolve_opponent_team_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    impl_properties! {
+        adapter,
+        contexts,
+        property_name,
+        OpponentTeam,
+        id,
+        name,
+    }
+}
+
+pub(super) fn resolve_opponent_player_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: Arc<AdapterInner<impl ClientTransport + 'a>>,
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    impl_properties! {
+        adapter,
+        contexts,
+        property_name,
+        OpponentPlayer,
+        id,
+        name,
+    }
+}
+
+/// Reports a property the adapter doesn't know, resolving it to `null`.
+pub(super) fn unknown_property<'a, V: AsVertex<Vertex> + 'a>(
+    adapter: &AdapterInner<impl ClientTransport>,
+    contexts: ContextIterator<'a, V>,
+    type_name: &str,
+    property_name: &str,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    adapter.report(AdapterError::UnknownProperty {
+        type_name: type_name.to_owned(),
+        property: property_name.to_owned(),
+    });
+    Box::new(contexts.map(|ctx| (ctx, FieldValue::Null)))
+}
@@ -0,0 +1,181 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use trustfall::FieldValue;
+
+use crate::adapter::{AdapterError, ErrorPolicy};
+
+/// A row of query results, keyed by output name.
+pub type Row = BTreeMap<Arc<str>, FieldValue>;
+
+/// Counts of the work done to execute a query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct QueryStats {
+    /// Requests sent to the API, including retries.
+    pub requests: u64,
+    /// Requests that were retried after a transient failure.
+    pub retries: u64,
+    /// Requests that failed for good.
+    pub failed_requests: u64,
+    /// Entities answered from the in-memory cache.
+    pub cache_hits: u64,
+    /// Entities that had to be fetched because they weren't cached.
+    pub cache_misses: u64,
+}
+
+/// The errors raised and work done while executing one query.
+#[derive(Debug, Default)]
+pub(crate) struct QueryState {
+    errors: Mutex<Vec<AdapterError>>,
+    /// Set once an error ends the query under [`ErrorPolicy::FailFast`].
+    failed: AtomicBool,
+    /// The error that ended the query, until it is handed to the caller.
+    failure: Mutex<Option<AdapterError>>,
+    /// Set once the consumer of the query's rows is gone.
+    cancelled: AtomicBool,
+    /// Whether the query's rows hand the error that ended it to the caller. Otherwise it is
+    /// recorded with the other errors.
+    yields_failure: bool,
+    requests: AtomicU64,
+    retries: AtomicU64,
+    failed_requests: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+}
+
+impl QueryState {
+    /// The state of a query whose rows end with the error that ended it, as [`QueryRows`] do.
+    pub(crate) fn for_rows() -> Self {
+        Self {
+            yields_failure: true,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn stats(&self) -> QueryStats {
+        QueryStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            failed_requests: self.failed_requests.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.cache_misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(super) fn count_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn count_retry(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn count_failed_request(&self) {
+        self.failed_requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn count_cache_lookup(&self, hit: bool) {
+        let counter = if hit {
+            &self.cache_hits
+        } else {
+            &self.cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn errors(&self) -> MutexGuard<'_, Vec<AdapterError>> {
+        self.errors.lock().unwrap()
+    }
+
+    pub(crate) fn take_errors(&self) -> Vec<AdapterError> {
+        std::mem::take(&mut *self.errors())
+    }
+
+    /// Handles `error` as `policy` says.
+    pub(super) fn report(&self, policy: ErrorPolicy, error: AdapterError) {
+        match policy {
+            ErrorPolicy::Collect => self.errors().push(error),
+            ErrorPolicy::FailFast => {
+                if !self.failed.swap(true, Ordering::SeqCst) && self.yields_failure {
+                    *self.failure.lock().unwrap() = Some(error);
+                } else {
+                    self.errors().push(error);
+                }
+            }
+            ErrorPolicy::Panic => panic!("{error}"),
+        }
+    }
+
+    /// Stops the query from sending any more requests.
+    pub(crate) fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the query was cancelled, or an error ended it, so nothing else should be fetched.
+    pub(super) fn is_stopped(&self) -> bool {
+        self.failed.load(Ordering::SeqCst) || self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn take_failure(&self) -> Option<AdapterError> {
+        self.failure.lock().unwrap().take()
+    }
+}
+
+/// The rows of a query executed with [`Adapter::execute_query`](crate::Adapter::execute_query).
+pub struct QueryRows<'a> {
+    rows: Box<dyn Iterator<Item = Row> + 'a>,
+    query: Arc<QueryState>,
+    done: bool,
+}
+
+impl<'a> QueryRows<'a> {
+    pub(super) fn new(rows: Box<dyn Iterator<Item = Row> + 'a>, query: Arc<QueryState>) -> Self {
+        Self {
+            rows,
+            query,
+            done: false,
+        }
+    }
+
+    /// Removes and returns the errors this query recorded so far.
+    pub fn take_errors(&self) -> Vec<AdapterError> {
+        self.query.take_errors()
+    }
+
+    /// The work done for this query so far.
+    pub fn stats(&self) -> QueryStats {
+        self.query.stats()
+    }
+}
+
+impl std::fmt::Debug for QueryRows<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryRows")
+            .field("query", &self.query)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for QueryRows<'_> {
+    type Item = Result<Row, AdapterError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let row = self.rows.next();
+        // A row produced after the failure may be missing data, so drop it.
+        if let Some(e) = self.query.take_failure() {
+            self.done = true;
+            return Some(Err(e));
+        }
+        row.map(Ok)
+    }
+}
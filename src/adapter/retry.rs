@@ -0,0 +1,272 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::{BuildHasher, RandomState},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use pandascore::endpoint::EndpointError;
+use reqwest::{
+    header::{HeaderValue, RETRY_AFTER},
+    Request, Response, StatusCode, Url,
+};
+use time::{format_description::well_known::Rfc2822, OffsetDateTime};
+use tower_service::Service;
+
+/// When and how often failed requests are retried.
+///
+/// The delay before each retry doubles, starting at the initial backoff and capped at the
+/// maximum backoff. With jitter, each delay is randomly shortened by up to half, so that
+/// concurrent requests don't retry in lockstep.
+///
+/// A failed request is never retried sooner than its response's `Retry-After` header asks for,
+/// whether it gives a number of seconds or a date. The client drops the headers of failed
+/// responses, so they are read by the transport returned by
+/// [`track_headers`](Self::track_headers), which the client must be created with. Without it,
+/// `Retry-After` is ignored, and [`AdapterBuilder::retry`](crate::AdapterBuilder::retry) logs a
+/// warning.
+///
+/// The default policy is [`none`](Self::none).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    statuses: Vec<StatusCode>,
+    transport_errors: bool,
+    retry_after: RetryAfter,
+}
+
+/// When the server allows failed requests again, by the URL of the request, as set by the
+/// `Retry-After` headers of their responses.
+///
+/// Clones share their deadlines, so that a policy and the transports it tracks agree.
+#[derive(Debug, Clone, Default)]
+struct RetryAfter {
+    deadlines: Arc<Mutex<HashMap<Url, Instant>>>,
+    /// Whether a transport feeds the headers in, set by [`RetryPolicy::track_headers`].
+    tracked: Arc<AtomicBool>,
+}
+
+impl RetryAfter {
+    fn insert(&self, url: Url, wait: Duration) {
+        let now = Instant::now();
+        let mut deadlines = self.deadlines.lock().unwrap();
+        deadlines.retain(|_, deadline| *deadline > now);
+        deadlines.insert(url, now + wait);
+    }
+
+    /// Takes how long the server asked to wait before requesting `url` again.
+    fn take(&self, url: &Url) -> Duration {
+        self.deadlines
+            .lock()
+            .unwrap()
+            .remove(url)
+            .map_or(Duration::ZERO, |deadline| {
+                deadline.saturating_duration_since(Instant::now())
+            })
+    }
+}
+
+// Deadlines are state rather than configuration, so they don't tell policies apart.
+impl PartialEq for RetryAfter {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for RetryAfter {}
+
+impl RetryPolicy {
+    /// Makes up to 3 attempts, backing off from 500ms up to 30s with jitter.
+    ///
+    /// Retries `429 Too Many Requests`, `500 Internal Server Error`, `502 Bad Gateway`,
+    /// `503 Service Unavailable` and `504 Gateway Timeout` responses, timeouts and connection
+    /// errors.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            transport_errors: true,
+            retry_after: RetryAfter::default(),
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self::new().max_attempts(1)
+    }
+
+    /// Sets how many times a request is made at most, including the first attempt.
+    #[must_use]
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry, and the most any retry is delayed by.
+    #[must_use]
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sets whether retry delays are randomized.
+    #[must_use]
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the response statuses that are retried.
+    #[must_use]
+    pub fn statuses(mut self, statuses: impl IntoIterator<Item = StatusCode>) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Sets whether timeouts and connection errors are retried.
+    #[must_use]
+    pub fn transport_errors(mut self, retry: bool) -> Self {
+        self.transport_errors = retry;
+        self
+    }
+
+    /// Wraps `transport` to read the `Retry-After` headers of failed responses, so that retries
+    /// wait at least as long as the server asks.
+    pub fn track_headers<T>(&self, transport: T) -> RetryAfterHeaders<T> {
+        self.retry_after.tracked.store(true, Ordering::Relaxed);
+        RetryAfterHeaders {
+            inner: transport,
+            retry_after: self.retry_after.clone(),
+        }
+    }
+
+    /// Whether the policy retries, but no transport reads `Retry-After` headers for it.
+    #[cfg(feature = "log")]
+    pub(super) fn ignores_retry_after(&self) -> bool {
+        self.max_attempts > 1 && !self.retry_after.tracked.load(Ordering::Relaxed)
+    }
+
+    /// Returns how long to wait before retrying a request that failed with `error` on its
+    /// `attempt`th attempt, or `None` if it shouldn't be retried.
+    pub(super) fn delay(&self, attempt: u32, error: &EndpointError) -> Option<Duration> {
+        let retry_after = match error {
+            EndpointError::Reqwest(e) => e
+                .url()
+                .map_or(Duration::ZERO, |url| self.retry_after.take(url)),
+            _ => Duration::ZERO,
+        };
+        if attempt >= self.max_attempts || !self.is_retryable(error) {
+            return None;
+        }
+
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2_u32.saturating_pow(attempt - 1))
+            .min(self.max_backoff);
+        let backoff = if self.jitter {
+            backoff.mul_f64(1.0 - random() / 2.0)
+        } else {
+            backoff
+        };
+        Some(backoff.max(retry_after))
+    }
+
+    fn is_retryable(&self, error: &EndpointError) -> bool {
+        let EndpointError::Reqwest(error) = error else {
+            return false;
+        };
+        match error.status() {
+            Some(status) => self.statuses.contains(&status),
+            None => self.transport_errors && (error.is_timeout() || error.is_connect()),
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// A transport that feeds the `Retry-After` headers of failed responses into a [`RetryPolicy`].
+///
+/// Created with [`RetryPolicy::track_headers`].
+#[derive(Debug, Clone)]
+pub struct RetryAfterHeaders<T> {
+    inner: T,
+    retry_after: RetryAfter,
+}
+
+impl<T> Service<Request> for RetryAfterHeaders<T>
+where
+    T: Service<Request, Response = Response, Error = reqwest::Error>,
+    T::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = reqwest::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, reqwest::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let url = req.url().clone();
+        let response = self.inner.call(req);
+        let retry_after = self.retry_after.clone();
+        Box::pin(async move {
+            let response = response.await?;
+            if !response.status().is_success() {
+                let wait = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(parse_retry_after);
+                if let Some(wait) = wait {
+                    retry_after.insert(url, wait);
+                }
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Reads how long a `Retry-After` header asks to wait, from either a number of seconds or an
+/// HTTP date.
+pub(crate) fn parse_retry_after(value: &HeaderValue) -> Option<Duration> {
+    let value = value.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = OffsetDateTime::parse(value, &Rfc2822).ok()?;
+    // A date in the past allows requests right away.
+    Some(
+        (date - OffsetDateTime::now_utc())
+            .try_into()
+            .unwrap_or_default(),
+    )
+}
+
+/// Returns a random number in `[0, 1)`, from the random keys of the standard library's hasher.
+fn random() -> f64 {
+    let bits = RandomState::new().hash_one(()) >> 11;
+    bits as f64 / (1_u64 << 53) as f64
+}
@@ -1,7 +1,108 @@
+use std::{
+    collections::BTreeMap,
+    future::Ready,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
 use pandascore::Client;
-use trustfall::provider::check_adapter_invariants;
+use reqwest::ResponseBuilderExt;
+use trustfall::{execute_query, provider::check_adapter_invariants, FieldValue};
+
+use crate::{adapter::Adapter, AdapterError, RateLimitHeaders};
+
+/// Transport that records every requested URL and answers with a canned body for its path,
+/// or an empty JSON list.
+///
+/// Paths of requests for a page other than the first are suffixed with `?page=<n>`.
+#[derive(Debug, Clone, Default)]
+struct MockTransport {
+    requests: Arc<Mutex<Vec<String>>>,
+    bodies: Arc<BTreeMap<String, String>>,
+    headers: Arc<BTreeMap<String, Vec<(&'static str, String)>>>,
+    /// Statuses to fail the next requests for a path with, in order.
+    failures: Arc<Mutex<BTreeMap<String, Vec<u16>>>>,
+}
+
+impl MockTransport {
+    fn with_body(mut self, path: &str, body: &str) -> Self {
+        Arc::make_mut(&mut self.bodies).insert(path.to_owned(), body.to_owned());
+        self
+    }
+
+    fn with_header(mut self, path: &str, name: &'static str, value: &str) -> Self {
+        Arc::make_mut(&mut self.headers)
+            .entry(path.to_owned())
+            .or_default()
+            .push((name, value.to_owned()));
+        self
+    }
+
+    fn with_failures(self, path: &str, statuses: &[u16]) -> Self {
+        let mut failures = self.failures.lock().unwrap();
+        failures.insert(path.to_owned(), statuses.iter().rev().copied().collect());
+        drop(failures);
+        self
+    }
 
-use crate::adapter::Adapter;
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl tower_service::Service<reqwest::Request> for MockTransport {
+    type Response = reqwest::Response;
+    type Error = reqwest::Error;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: reqwest::Request) -> Self::Future {
+        self.requests.lock().unwrap().push(req.url().to_string());
+        let mut path = req.url().path().to_owned();
+        if let Some((_, page)) = req.url().query_pairs().find(|(k, _)| k == "page") {
+            path = format!("{path}?page={page}");
+        }
+
+        let body = self
+            .bodies
+            .get(&path)
+            .cloned()
+            .unwrap_or_else(|| "[]".to_owned());
+        let status = self
+            .failures
+            .lock()
+            .unwrap()
+            .get_mut(&path)
+            .and_then(Vec::pop)
+            .unwrap_or(200);
+        let mut response = http::Response::builder()
+            .status(status)
+            .url(req.url().clone());
+        for (name, value) in self.headers.get(&path).into_iter().flatten() {
+            response = response.header(*name, value);
+        }
+        std::future::ready(Ok(response.body(body).unwrap().into()))
+    }
+}
+
+fn run(transport: &MockTransport, query: &str, args: BTreeMap<&str, FieldValue>) -> usize {
+    let adapter = Arc::new(Adapter::new(
+        Client::new(transport.clone(), "token").unwrap(),
+    ));
+    execute_query(Adapter::<MockTransport>::schema(), adapter, query, args)
+        .unwrap()
+        .count()
+}
+
+/// Splits a URL into its path and query pairs, since query parameter order isn't stable.
+fn parse_url(url: &str) -> (String, BTreeMap<String, String>) {
+    let url = reqwest::Url::parse(url).unwrap();
+    let query = url.query_pairs().into_owned().collect();
+    (url.path().to_owned(), query)
+}
 
 #[test]
 fn adapter_satisfies_trustfall_invariants() {
@@ -9,3 +110,1505 @@ fn adapter_satisfies_trustfall_invariants() {
     let schema = Adapter::<reqwest::Client>::schema();
     check_adapter_invariants(schema, adapter);
 }
+
+#[test]
+fn match_opponents_resolve_to_their_teams() {
+    let transport = MockTransport::default()
+        .with_body("/matches/", &format!("[{}]", match_json(7)))
+        .with_body(
+            "/matches/7/opponents",
+            &format!(r#"{{ "opponent_type": "Team", "opponents": [{TEAM}] }}"#),
+        )
+        .with_body("/teams", &format!("[{TEAM}]"));
+    let adapter = Arc::new(Adapter::new(Client::new(transport, "token").unwrap()));
+    let query = r#"{
+        Matches {
+            opponents {
+                ... on OpponentTeam {
+                    opponent_id: id @output
+                    name @output
+                    team { team_id: id @output }
+                }
+            }
+        }
+    }"#;
+    let rows: Vec<_> = execute_query(
+        Adapter::<MockTransport>::schema(),
+        adapter,
+        query,
+        BTreeMap::<&str, FieldValue>::new(),
+    )
+    .unwrap()
+    .map(|row| {
+        (
+            row["opponent_id"].clone(),
+            row["name"].clone(),
+            row["team_id"].clone(),
+        )
+    })
+    .collect();
+
+    assert_eq!(
+        rows,
+        [(
+            FieldValue::Uint64(5),
+            FieldValue::from("T"),
+            FieldValue::Uint64(5)
+        )]
+    );
+}
+
+#[test]
+fn tournament_rosters_list_teams_with_their_players() {
+    let roster = TEAM.replace(
+        r#""players": []"#,
+        &format!("\"players\": [{}]", player_json(9)),
+    );
+    let transport = MockTransport::default()
+        .with_body("/tournaments/", &format!("[{}]", tournament_json(1)))
+        .with_body(
+            "/tournaments/1/rosters",
+            &format!(r#"{{ "type": "Team", "rosters": [{roster}] }}"#),
+        )
+        .with_body("/players", &format!("[{}]", player_json(9)));
+    let adapter = Arc::new(Adapter::new(Client::new(transport, "token").unwrap()));
+    let query = r#"{
+        Tournaments {
+            rosters {
+                team { team_id: id @output }
+                players { player: name @output }
+            }
+        }
+    }"#;
+    let rows: Vec<_> = execute_query(
+        Adapter::<MockTransport>::schema(),
+        adapter,
+        query,
+        BTreeMap::<&str, FieldValue>::new(),
+    )
+    .unwrap()
+    .map(|row| (row["team_id"].clone(), row["player"].clone()))
+    .collect();
+
+    assert_eq!(rows, [(FieldValue::Uint64(5), FieldValue::from("P9"))]);
+}
+
+#[test]
+fn match_results_carry_scores_and_resolve_their_teams() {
+    let results = r#""results": [{ "score": 2, "team_id": 5 }, { "score": 1, "team_id": 6 }]"#;
+    let transport = MockTransport::default()
+        .with_body(
+            "/matches/",
+            &format!("[{}]", match_json(7).replace(r#""results": []"#, results)),
+        )
+        .with_body(
+            "/teams",
+            &format!("[{TEAM}, {}]", TEAM.replace(r#""id": 5"#, r#""id": 6"#)),
+        );
+    let adapter = Arc::new(Adapter::new(
+        Client::new(transport.clone(), "token").unwrap(),
+    ));
+    let query = r#"{
+        Matches {
+            results {
+                opponent_id @output
+                score @output
+                team { team_id: id @output }
+            }
+        }
+    }"#;
+    let rows: Vec<_> = execute_query(
+        Adapter::<MockTransport>::schema(),
+        adapter,
+        query,
+        BTreeMap::<&str, FieldValue>::new(),
+    )
+    .unwrap()
+    .map(|row| {
+        (
+            row["opponent_id"].clone(),
+            row["score"].clone(),
+            row["team_id"].clone(),
+        )
+    })
+    .collect();
+
+    let expected = [(5, 2, 5), (6, 1, 6)].map(|(opponent, score, team)| {
+        (
+            FieldValue::Uint64(opponent),
+            FieldValue::Uint64(score),
+            FieldValue::Uint64(team),
+        )
+    });
+    assert_eq!(rows, expected);
+    // Both teams are fetched in one request.
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[test]
+fn root_entrypoints_route_games_to_their_endpoints() {
+    let entrypoints = [
+        "Leagues",
+        "Series",
+        "Tournaments",
+        "Matches",
+        "RunningMatches",
+        "UpcomingMatches",
+        "PastMatches",
+        "Teams",
+        "Players",
+    ];
+    for entrypoint in entrypoints {
+        let transport = MockTransport::default();
+        let query = format!(r#"{{ {entrypoint}(game: "rl") {{ id @output }} }}"#);
+        run(&transport, &query, BTreeMap::new());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1, "{entrypoint}");
+        let (path, _) = parse_url(&requests[0]);
+        assert!(path.starts_with("/rl/"), "{entrypoint} requested {path}");
+    }
+
+    // Games without their own endpoints are listed across all games, filtered by game.
+    for entrypoint in entrypoints {
+        let transport = MockTransport::default();
+        let query = format!(r#"{{ {entrypoint}(game: "dota2") {{ id @output }} }}"#);
+        run(&transport, &query, BTreeMap::new());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1, "{entrypoint}");
+        let (path, query) = parse_url(&requests[0]);
+        assert!(
+            !path.starts_with("/dota2/"),
+            "{entrypoint} requested {path}"
+        );
+        assert_eq!(query["filter[videogame]"], "4", "{entrypoint}");
+    }
+
+    // Unknown games are reported rather than listed across all games.
+    let transport = MockTransport::default();
+    let client = Client::new(transport.clone(), "token").unwrap();
+    let adapter = Adapter::new(client);
+    let mut query_rows = adapter
+        .execute_query(
+            r#"{ Teams(game: "chess") { id @output } }"#,
+            BTreeMap::<&str, FieldValue>::new(),
+        )
+        .unwrap();
+    assert_eq!(query_rows.by_ref().count(), 0);
+    assert!(matches!(
+        query_rows.take_errors().as_slice(),
+        [AdapterError::InvalidGame(game)] if game == "chess"
+    ));
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn id_equality_filter_uses_direct_get() {
+    let transport = MockTransport::default();
+    let query = r#"{ Teams { id @filter(op: "=", value: ["$id"]) @output } }"#;
+    run(
+        &transport,
+        query,
+        BTreeMap::from([("id", FieldValue::Int64(5))]),
+    );
+
+    assert_eq!(transport.requests(), ["https://api.pandascore.co/teams/5"]);
+}
+
+#[test]
+fn id_one_of_filter_uses_id_filter_option() {
+    let transport = MockTransport::default();
+    let query = r#"{ Matches(game: "lol") { id @filter(op: "one_of", value: ["$ids"]) @output } }"#;
+    let ids = FieldValue::List(vec![FieldValue::Int64(1), FieldValue::Int64(2)].into());
+    run(&transport, query, BTreeMap::from([("ids", ids)]));
+
+    assert_eq!(
+        transport.requests(),
+        ["https://api.pandascore.co/lol/matches/?filter%5Bid%5D=1%2C2"]
+    );
+}
+
+#[test]
+fn property_filters_use_filter_and_range_options() {
+    let transport = MockTransport::default();
+    let query = r#"{
+        Series(game: "lol") {
+            slug @filter(op: "one_of", value: ["$slugs"]) @output
+            year @filter(op: ">=", value: ["$year"]) @output
+            begin_at @filter(op: ">", value: ["$since"]) @output
+        }
+    }"#;
+    let slugs = FieldValue::List(vec![FieldValue::from("a"), FieldValue::from("b")].into());
+    let args = BTreeMap::from([
+        ("slugs", slugs),
+        ("year", FieldValue::Int64(2020)),
+        ("since", FieldValue::from("2020-01-01T00:00:00Z")),
+    ]);
+    run(&transport, query, args);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    let (path, query) = parse_url(&requests[0]);
+    assert_eq!(path, "/lol/series/");
+    assert_eq!(
+        query,
+        BTreeMap::from([
+            ("filter[slug]".to_owned(), "a,b".to_owned()),
+            ("range[year]".to_owned(), format!("2020,{}", i64::MAX)),
+        ])
+    );
+}
+
+#[test]
+fn unknown_enum_values_are_not_pushed_down() {
+    let transport = MockTransport::default();
+    let query = r#"{
+        Matches {
+            match_type @filter(op: "one_of", value: ["$types"]) @output
+            match_status @filter(op: "=", value: ["$status"]) @output
+            forfeit @filter(op: "=", value: ["$forfeit"])
+        }
+    }"#;
+    let types =
+        FieldValue::List(vec![FieldValue::from("best_of"), FieldValue::from("unknown")].into());
+    let args = BTreeMap::from([
+        ("types", types),
+        ("status", FieldValue::from("unknown")),
+        ("forfeit", FieldValue::Boolean(false)),
+    ]);
+    run(&transport, query, args);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    let (_, query) = parse_url(&requests[0]);
+    assert_eq!(
+        query,
+        BTreeMap::from([("filter[forfeit]".to_owned(), "false".to_owned())])
+    );
+}
+
+/// Body of `GET /teams/5`.
+const TEAM: &str = r#"{
+    "acronym": null, "id": 5, "image_url": null, "location": null,
+    "modified_at": "2020-01-01T00:00:00Z", "name": "T", "slug": null,
+    "current_videogame": null, "players": []
+}"#;
+
+#[test]
+fn neighbor_filters_are_pushed_into_edge_requests() {
+    let transport = MockTransport::default().with_body("/teams/5", TEAM);
+    let query = r#"{
+        Teams {
+            id @filter(op: "=", value: ["$team"])
+            matches {
+                id @filter(op: "one_of", value: ["$ids"]) @output
+                match_status @filter(op: "=", value: ["$status"])
+                number_of_games @filter(op: "<", value: ["$games"])
+            }
+        }
+    }"#;
+    let ids = FieldValue::List(vec![FieldValue::Int64(1), FieldValue::Int64(2)].into());
+    let args = BTreeMap::from([
+        ("team", FieldValue::Int64(5)),
+        ("ids", ids),
+        ("status", FieldValue::from("finished")),
+        ("games", FieldValue::Int64(3)),
+    ]);
+    run(&transport, query, args);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let (path, query) = parse_url(&requests[1]);
+    assert_eq!(path, "/teams/5/matches");
+    assert_eq!(
+        query,
+        BTreeMap::from([
+            ("filter[id]".to_owned(), "1,2".to_owned()),
+            ("filter[status]".to_owned(), "finished".to_owned()),
+            ("range[number_of_games]".to_owned(), "0,2".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn edge_parameters_are_checked_locally() {
+    let begin = |id: u64, status: &str, begin_at: &str| {
+        match_json(id)
+            .replacen(
+                r#""begin_at": null"#,
+                &format!(r#""begin_at": "{begin_at}""#),
+                1,
+            )
+            .replace(
+                r#""status": "finished""#,
+                &format!(r#""status": "{status}""#),
+            )
+    };
+    let transport = MockTransport::default()
+        .with_body("/teams/5", TEAM)
+        .with_body(
+            "/teams/5/matches",
+            &format!(
+                "[{}, {}, {}]",
+                begin(1, "running", "2024-03-01T00:00:00Z"),
+                begin(2, "finished", "2024-02-01T00:00:00Z"),
+                begin(3, "running", "2023-12-01T00:00:00Z"),
+            ),
+        )
+        .with_header(
+            "/teams/5/matches",
+            "Link",
+            r#"<https://api.pandascore.co/teams/5/matches?page=2>; rel="next""#,
+        );
+    let query = r#"{
+        Teams {
+            id @filter(op: "=", value: ["$team"])
+            matches(status: "running", since: "2024-01-01T00:00:00Z") {
+                id @output
+                match_status @filter(op: "one_of", value: ["$statuses"])
+            }
+        }
+    }"#;
+    let run_with = |statuses: &[&str]| {
+        let statuses = statuses.iter().map(|&s| FieldValue::from(s)).collect();
+        let args = BTreeMap::from([
+            ("team", FieldValue::Int64(5)),
+            ("statuses", FieldValue::List(statuses)),
+        ]);
+        run(&transport, query, args)
+    };
+
+    // The API may answer with other statuses, and a match before `since` ends the listing, so
+    // the second page is never requested.
+    assert_eq!(run_with(&["running", "finished"]), 1);
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let (_, query) = parse_url(&requests[1]);
+    assert_eq!(
+        query,
+        BTreeMap::from([
+            ("filter[status]".to_owned(), "running".to_owned()),
+            ("sort".to_owned(), "-begin_at".to_owned()),
+        ])
+    );
+
+    // A status filter that contradicts the parameter matches nothing, without a request.
+    assert_eq!(run_with(&["finished"]), 0);
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[test]
+fn edge_parameters_map_onto_collection_options() {
+    let transport = MockTransport::default().with_body("/teams/5", TEAM);
+    let query = r#"{
+        Teams {
+            id @filter(op: "=", value: ["$team"])
+            matches(sort: "-begin_at", limit: 5, status: "running", since: "2024-01-01T00:00:00Z") {
+                id @output
+            }
+        }
+    }"#;
+    run(
+        &transport,
+        query,
+        BTreeMap::from([("team", FieldValue::Int64(5))]),
+    );
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let (path, query) = parse_url(&requests[1]);
+    assert_eq!(path, "/teams/5/matches");
+    assert_eq!(
+        query,
+        BTreeMap::from([
+            ("filter[status]".to_owned(), "running".to_owned()),
+            ("per_page".to_owned(), "5".to_owned()),
+            ("sort".to_owned(), "-begin_at".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn root_parameters_map_onto_collection_options() {
+    let transport = MockTransport::default();
+    let query = r#"{
+        Teams(
+            game: "rl"
+            search: "g2"
+            search_field: "acronym"
+            slug: "g2-esports"
+            ids: [1, 2]
+            sort: "name"
+            page_size: 10
+        ) {
+            id @filter(op: "one_of", value: ["$ids"]) @output
+        }
+    }"#;
+    let ids = FieldValue::List(vec![FieldValue::Int64(2), FieldValue::Int64(3)].into());
+    run(&transport, query, BTreeMap::from([("ids", ids)]));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    let (path, query) = parse_url(&requests[0]);
+    assert_eq!(path, "/rl/teams");
+    assert_eq!(
+        query,
+        BTreeMap::from([
+            ("filter[id]".to_owned(), "2".to_owned()),
+            ("filter[slug]".to_owned(), "g2-esports".to_owned()),
+            ("per_page".to_owned(), "10".to_owned()),
+            ("search[acronym]".to_owned(), "g2".to_owned()),
+            ("sort".to_owned(), "name".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn root_parameters_are_checked_locally() {
+    let series = |id: u64, slug: &str, begin_at: &str| {
+        series_json(id, 1)
+            .replace(r#""slug": "s""#, &format!(r#""slug": "{slug}""#))
+            .replacen(
+                r#""begin_at": null"#,
+                &format!(r#""begin_at": "{begin_at}""#),
+                1,
+            )
+    };
+    let transport = MockTransport::default()
+        .with_body(
+            "/series/",
+            &format!(
+                "[{}, {}, {}]",
+                series(1, "a", "2024-03-01T00:00:00Z"),
+                series(2, "b", "2024-02-01T00:00:00Z"),
+                series(3, "a", "2023-12-01T00:00:00Z"),
+            ),
+        )
+        .with_header(
+            "/series/",
+            "Link",
+            r#"<https://api.pandascore.co/series/?page=2>; rel="next""#,
+        );
+    let query = r#"{
+        Series(slug: "a", since: "2024-01-01T00:00:00Z") {
+            id @output
+            slug @filter(op: "one_of", value: ["$slugs"])
+        }
+    }"#;
+    let run_with = |slugs: &[&str]| {
+        let slugs = slugs.iter().map(|&s| FieldValue::from(s)).collect();
+        run(
+            &transport,
+            query,
+            BTreeMap::from([("slugs", FieldValue::List(slugs))]),
+        )
+    };
+
+    assert_eq!(run_with(&["a", "b"]), 1);
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    let (_, query) = parse_url(&requests[0]);
+    assert_eq!(
+        query,
+        BTreeMap::from([
+            ("filter[slug]".to_owned(), "a".to_owned()),
+            ("sort".to_owned(), "-begin_at".to_owned()),
+        ])
+    );
+
+    assert_eq!(run_with(&["b"]), 0);
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[test]
+fn status_entrypoints_filter_matches_by_status() {
+    let link = |page: u32| {
+        format!(
+            "<https://api.pandascore.co/lol/matches/?filter[status]=finished&sort=-begin_at\
+             &page={page}>; rel=\"next\""
+        )
+    };
+    let transport = MockTransport::default()
+        .with_body("/lol/matches/", &format!("[{}]", match_json(1)))
+        .with_header("/lol/matches/", "Link", &link(2))
+        .with_body("/lol/matches/?page=2", &format!("[{}]", match_json(2)))
+        .with_header("/lol/matches/?page=2", "Link", &link(3))
+        .with_body("/lol/matches/?page=3", &format!("[{}]", match_json(3)));
+    let rows = run(
+        &transport,
+        r#"{ PastMatches(game: "lol", sort: "-begin_at") { id @output } }"#,
+        BTreeMap::new(),
+    );
+
+    assert_eq!(rows, 3);
+    let requests: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|url| parse_url(url))
+        .collect();
+    for (i, (path, query)) in requests.iter().enumerate() {
+        assert_eq!(path, "/lol/matches/");
+        assert_eq!(query["filter[status]"], "finished");
+        assert_eq!(query["sort"], "-begin_at");
+        assert_eq!(
+            query.get("page").cloned(),
+            (i > 0).then(|| (i + 1).to_string())
+        );
+    }
+    assert_eq!(requests.len(), 3);
+
+    let transport = MockTransport::default();
+    run(
+        &transport,
+        r#"{ RunningMatches { id @output } }"#,
+        BTreeMap::new(),
+    );
+    run(
+        &transport,
+        r#"{ UpcomingMatches { id @output } }"#,
+        BTreeMap::new(),
+    );
+    let statuses: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|url| parse_url(url).1["filter[status]"].clone())
+        .collect();
+    assert_eq!(statuses, ["running", "not_started"]);
+}
+
+#[test]
+fn status_entrypoints_check_the_status_locally() {
+    let transport = MockTransport::default().with_body(
+        "/matches/",
+        &format!(
+            "[{}, {}]",
+            match_json(1).replace(r#""status": "finished""#, r#""status": "running""#),
+            match_json(2)
+        ),
+    );
+    let query = r#"{
+        RunningMatches {
+            id @output
+            match_status @filter(op: "one_of", value: ["$statuses"])
+        }
+    }"#;
+    let run_with = |statuses: &[&str]| {
+        let statuses = statuses.iter().map(|&s| FieldValue::from(s)).collect();
+        let args = BTreeMap::from([("statuses", FieldValue::List(statuses))]);
+        run(&transport, query, args)
+    };
+
+    assert_eq!(run_with(&["running", "finished"]), 1);
+    let (_, query) = parse_url(&transport.requests()[0]);
+    assert_eq!(
+        query,
+        BTreeMap::from([("filter[status]".to_owned(), "running".to_owned())])
+    );
+    assert_eq!(run_with(&["finished"]), 0);
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[test]
+fn tournament_and_series_status_entrypoints_check_their_schedule() {
+    let tournament = |id: u64, begin_at: &str, end_at: &str| {
+        tournament_json(id)
+            .replacen(
+                r#""begin_at": null"#,
+                &format!(r#""begin_at": {begin_at}"#),
+                1,
+            )
+            .replacen(r#""end_at": null"#, &format!(r#""end_at": {end_at}"#), 1)
+    };
+    let transport = MockTransport::default()
+        .with_body(
+            "/tournaments/",
+            &format!(
+                "[{}, {}, {}, {}]",
+                tournament(1, r#""2999-01-01T00:00:00Z""#, "null"),
+                tournament(2, r#""2020-01-01T00:00:00Z""#, "null"),
+                tournament(3, r#""2020-01-01T00:00:00Z""#, r#""2021-01-01T00:00:00Z""#),
+                tournament(4, r#""2019-01-01T00:00:00Z""#, r#""2999-01-01T00:00:00Z""#),
+            ),
+        )
+        .with_header(
+            "/tournaments/",
+            "Link",
+            r#"<https://api.pandascore.co/tournaments/?page=2>; rel="next""#,
+        );
+    let ids = |entrypoint: &str| {
+        let adapter = Arc::new(Adapter::new(
+            Client::new(transport.clone(), "token").unwrap(),
+        ));
+        let query = format!("{{ {entrypoint} {{ id @output }} }}");
+        execute_query(
+            Adapter::<MockTransport>::schema(),
+            adapter,
+            query.as_str(),
+            BTreeMap::<&str, FieldValue>::new(),
+        )
+        .unwrap()
+        .map(|row| row["id"].clone())
+        .collect::<Vec<_>>()
+    };
+
+    // Upcoming tournaments are listed newest first, so the first one begun ends the listing.
+    assert_eq!(ids("UpcomingTournaments"), [FieldValue::Uint64(1)]);
+    let (_, query) = parse_url(&transport.requests()[0]);
+    assert_eq!(query["sort"], "-begin_at");
+    assert_eq!(transport.requests().len(), 1);
+
+    let running = ids("RunningTournaments");
+    assert_eq!(running, [FieldValue::Uint64(2), FieldValue::Uint64(4)]);
+    let past = ids("PastTournaments");
+    assert_eq!(past, [FieldValue::Uint64(3)]);
+    let sorts: Vec<_> = transport
+        .requests()
+        .iter()
+        .filter_map(|url| parse_url(url).1.remove("sort"))
+        .collect();
+    assert_eq!(sorts, ["-begin_at", "-begin_at", "-end_at"]);
+
+    let transport = MockTransport::default();
+    for entrypoint in ["RunningSeries", "UpcomingSeries", "PastSeries"] {
+        run(
+            &transport,
+            &format!("{{ {entrypoint} {{ id @output }} }}"),
+            BTreeMap::new(),
+        );
+    }
+    let paths: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|url| parse_url(url).0)
+        .collect();
+    assert_eq!(paths, ["/series/", "/series/", "/series/"]);
+}
+
+#[test]
+fn next_links_that_lose_the_page_are_not_followed() {
+    let transport = MockTransport::default()
+        .with_body("/leagues", &format!("[{}]", league_json(1)))
+        .with_header(
+            "/leagues",
+            "Link",
+            r#"<https://api.pandascore.co/leagues>; rel="next""#,
+        );
+    let rows = run(&transport, r#"{ Leagues { id @output } }"#, BTreeMap::new());
+
+    assert_eq!(rows, 1);
+    assert_eq!(transport.requests().len(), 1);
+}
+
+/// Body of a league with `id`.
+fn league_json(id: u64) -> String {
+    format!(
+        r#"{{
+            "id": {id}, "image_url": null, "modified_at": "2020-01-01T00:00:00Z", "name": "L",
+            "slug": "l", "url": null, "series": [],
+            "videogame": {{ "id": 1, "name": "LoL", "slug": "league-of-legends",
+                            "current_version": null }}
+        }}"#
+    )
+}
+
+/// Body of a series with `id` in the league with `league_id`.
+fn series_json(id: u64, league_id: u64) -> String {
+    format!(
+        r#"{{
+            "begin_at": null, "end_at": null, "full_name": "S", "id": {id},
+            "league_id": {league_id}, "modified_at": "2020-01-01T00:00:00Z", "name": null,
+            "season": null, "slug": "s", "winner_id": null, "winner_type": "Team", "year": 2020,
+            "league": {{ "id": {league_id}, "image_url": null,
+                         "modified_at": "2020-01-01T00:00:00Z", "name": "L", "slug": "l",
+                         "url": null }},
+            "tournaments": [],
+            "videogame": {{ "id": 1, "name": "LoL", "slug": "league-of-legends",
+                            "current_version": null }}
+        }}"#
+    )
+}
+
+/// Body of a match with `id` in league, series and tournament 1.
+fn match_json(id: u64) -> String {
+    format!(
+        r#"{{
+            "begin_at": null, "detailed_stats": false, "draw": false, "end_at": null,
+            "forfeit": false, "game_advantage": null, "id": {id},
+            "live": {{ "opens_at": null, "supported": false, "url": null }},
+            "match_type": "best_of", "modified_at": "2020-01-01T00:00:00Z", "name": "M",
+            "number_of_games": 3, "original_scheduled_at": null, "rescheduled": null,
+            "scheduled_at": null, "slug": "m", "status": "finished", "tournament_id": 1,
+            "winner_id": null, "winner_type": "Team", "league_id": 1, "results": [],
+            "serie_id": 1, "videogame_version": null,
+            "league": {{ "id": 1, "image_url": null, "modified_at": "2020-01-01T00:00:00Z",
+                         "name": "L", "slug": "l", "url": null }},
+            "serie": {{ "begin_at": null, "end_at": null, "full_name": "S", "id": 1,
+                        "league_id": 1, "modified_at": "2020-01-01T00:00:00Z", "name": null,
+                        "season": null, "slug": "s", "winner_id": null, "winner_type": "Team",
+                        "year": 2020 }},
+            "tournament": {{ "begin_at": null, "end_at": null, "detailed_stats": false,
+                             "has_bracket": false, "id": 1, "league_id": 1,
+                             "live_supported": false, "modified_at": "2020-01-01T00:00:00Z",
+                             "name": "T", "prizepool": null, "serie_id": 1, "slug": "t",
+                             "tier": null, "winner_id": null, "winner_type": "Team" }},
+            "videogame": {{ "id": 1, "name": "LoL", "slug": "league-of-legends",
+                            "current_version": null }}
+        }}"#
+    )
+}
+
+/// Body of a tournament with `id` in league and series 1.
+fn tournament_json(id: u64) -> String {
+    format!(
+        r#"{{
+            "begin_at": null, "end_at": null, "detailed_stats": false, "has_bracket": false,
+            "id": {id}, "league_id": 1, "live_supported": false,
+            "modified_at": "2020-01-01T00:00:00Z", "name": "T", "prizepool": null,
+            "serie_id": 1, "slug": "t", "tier": null, "winner_id": null, "winner_type": "Team",
+            "matches": [], "teams": [],
+            "league": {{ "id": 1, "image_url": null, "modified_at": "2020-01-01T00:00:00Z",
+                         "name": "L", "slug": "l", "url": null }},
+            "serie": {{ "begin_at": null, "end_at": null, "full_name": "S", "id": 1,
+                        "league_id": 1, "modified_at": "2020-01-01T00:00:00Z", "name": null,
+                        "season": null, "slug": "s", "winner_id": null, "winner_type": "Team",
+                        "year": 2020 }},
+            "videogame": {{ "id": 1, "name": "LoL", "slug": "league-of-legends",
+                            "current_version": null }}
+        }}"#
+    )
+}
+
+/// Body of a player with `id` and no current team.
+fn player_json(id: u64) -> String {
+    format!(
+        r#"{{
+            "active": true, "age": null, "birthday": null, "first_name": null, "id": {id},
+            "image_url": null, "last_name": null, "modified_at": "2020-01-01T00:00:00Z",
+            "name": "P{id}", "nationality": null, "role": null, "slug": null,
+            "current_team": null, "current_videogame": null
+        }}"#
+    )
+}
+
+#[test]
+fn cache_reuses_listed_entities_for_gets() {
+    use crate::CacheConfig;
+
+    let transport = MockTransport::default()
+        .with_body("/leagues", &format!("[{}]", league_json(1)))
+        .with_body(
+            "/leagues/1/series",
+            &format!("[{}, {}]", series_json(1, 1), series_json(2, 1)),
+        );
+    let query = r#"{ Leagues { series { league { id @output } } } }"#;
+    let paths = |transport: &MockTransport| -> Vec<_> {
+        transport
+            .requests()
+            .iter()
+            .map(|url| parse_url(url).0)
+            .collect()
+    };
+
+    let adapter = Arc::new(
+        Adapter::builder(Client::new(transport.clone(), "token").unwrap())
+            .cache(CacheConfig::default())
+            .build(),
+    );
+    let schema = Adapter::<MockTransport>::schema();
+    let rows = execute_query(schema, adapter, query, BTreeMap::<&str, FieldValue>::new())
+        .unwrap()
+        .count();
+    assert_eq!(rows, 2);
+    assert_eq!(paths(&transport), ["/leagues", "/leagues/1/series"]);
+
+    // The cache is opt-in.
+    let transport = MockTransport::default()
+        .with_body("/leagues", &format!("[{}]", league_json(1)))
+        .with_body(
+            "/leagues/1/series",
+            &format!("[{}, {}]", series_json(1, 1), series_json(2, 1)),
+        );
+    assert_eq!(run(&transport, query, BTreeMap::new()), 2);
+    assert_eq!(
+        paths(&transport),
+        ["/leagues", "/leagues/1/series", "/leagues"]
+    );
+}
+
+#[cfg(feature = "disk-cache")]
+#[test]
+fn disk_cache_replays_responses_and_works_offline() {
+    use crate::{DiskCache, DiskCacheConfig};
+
+    let dir = std::env::temp_dir().join(format!("pandascore-disk-cache-{}", std::process::id()));
+    let transport = MockTransport::default().with_body("/teams/5", TEAM);
+    let query = r#"{ Teams { id @filter(op: "=", value: ["$id"]) @output } }"#;
+    let run_cached = |config: DiskCacheConfig| {
+        let client = Client::new(DiskCache::new(transport.clone(), config), "token").unwrap();
+        let adapter = Arc::new(Adapter::new(client));
+        let args = BTreeMap::from([("id", FieldValue::Int64(5))]);
+        let rows = execute_query(
+            Adapter::<DiskCache<MockTransport>>::schema(),
+            adapter.clone(),
+            query,
+            args,
+        )
+        .unwrap()
+        .count();
+        let errors = adapter.errors().len();
+        (rows, errors)
+    };
+
+    assert_eq!(run_cached(DiskCacheConfig::new(&dir)), (1, 0));
+    assert_eq!(run_cached(DiskCacheConfig::new(&dir).offline(true)), (1, 0));
+    assert_eq!(transport.requests().len(), 1);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(run_cached(DiskCacheConfig::new(&dir).offline(true)), (0, 1));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[cfg(feature = "disk-cache")]
+#[test]
+fn disk_cache_ignores_the_order_of_query_parameters() {
+    use crate::{DiskCache, DiskCacheConfig};
+
+    let dir = std::env::temp_dir().join(format!(
+        "pandascore-disk-cache-order-{}",
+        std::process::id()
+    ));
+    let transport = MockTransport::default().with_body("/teams", &format!("[{TEAM}]"));
+    let query = r#"{
+        Teams {
+            acronym @filter(op: "=", value: ["$acronym"])
+            location @filter(op: "=", value: ["$location"])
+            name @filter(op: "=", value: ["$name"]) @output
+            slug @filter(op: "=", value: ["$slug"])
+        }
+    }"#;
+    // Each adapter builds its options in a fresh map, whose iteration order varies.
+    for _ in 0..8 {
+        let client = Client::new(
+            DiskCache::new(transport.clone(), DiskCacheConfig::new(&dir)),
+            "token",
+        )
+        .unwrap();
+        let adapter = Arc::new(Adapter::new(client));
+        let args = BTreeMap::from([
+            ("acronym", FieldValue::from("T")),
+            ("location", FieldValue::from("FR")),
+            ("name", FieldValue::from("Team")),
+            ("slug", FieldValue::from("team")),
+        ]);
+        execute_query(
+            Adapter::<DiskCache<MockTransport>>::schema(),
+            adapter,
+            query,
+            args,
+        )
+        .unwrap()
+        .for_each(drop);
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(transport.requests().len(), 1);
+    let (_, query) = parse_url(&transport.requests()[0]);
+    assert_eq!(query["filter[acronym]"], "T");
+    assert_eq!(query["filter[location]"], "FR");
+}
+
+#[test]
+fn single_neighbor_edges_are_fetched_in_batches() {
+    let transport = MockTransport::default()
+        .with_body(
+            "/series/",
+            &format!(
+                "[{}, {}, {}]",
+                series_json(1, 1),
+                series_json(2, 2),
+                series_json(3, 1)
+            ),
+        )
+        .with_body(
+            "/leagues",
+            &format!("[{}, {}]", league_json(2), league_json(1)),
+        );
+    let adapter = Arc::new(Adapter::new(
+        Client::new(transport.clone(), "token").unwrap(),
+    ));
+    let query = r#"{ Series { id @output league { league_id: id @output } } }"#;
+    let rows: Vec<_> = execute_query(
+        Adapter::<MockTransport>::schema(),
+        adapter,
+        query,
+        BTreeMap::<&str, FieldValue>::new(),
+    )
+    .unwrap()
+    .map(|row| (row["id"].clone(), row["league_id"].clone()))
+    .collect();
+
+    let expected = [(1, 1), (2, 2), (3, 1)]
+        .map(|(id, league)| (FieldValue::Uint64(id), FieldValue::Uint64(league)));
+    assert_eq!(rows, expected);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let (path, query) = parse_url(&requests[1]);
+    assert_eq!(path, "/leagues");
+    assert_eq!(
+        query,
+        BTreeMap::from([
+            ("filter[id]".to_owned(), "1,2".to_owned()),
+            ("per_page".to_owned(), "2".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn winners_are_fetched_in_batches() {
+    let won = |id| series_json(id, 1).replace(r#""winner_id": null"#, r#""winner_id": 5"#);
+    let transport = MockTransport::default()
+        .with_body(
+            "/series/",
+            &format!("[{}, {}, {}]", won(1), series_json(2, 1), won(3)),
+        )
+        .with_body("/teams", &format!("[{TEAM}]"));
+    let adapter = Arc::new(Adapter::new(
+        Client::new(transport.clone(), "token").unwrap(),
+    ));
+    let query = r#"{
+        Series {
+            id @output
+            winner { ... on WinnerTeam { team { team_id: id @output } } }
+        }
+    }"#;
+    let rows: Vec<_> = execute_query(
+        Adapter::<MockTransport>::schema(),
+        adapter,
+        query,
+        BTreeMap::<&str, FieldValue>::new(),
+    )
+    .unwrap()
+    .map(|row| (row["id"].clone(), row["team_id"].clone()))
+    .collect();
+
+    let expected =
+        [(1, 5), (3, 5)].map(|(id, team)| (FieldValue::Uint64(id), FieldValue::Uint64(team)));
+    assert_eq!(rows, expected);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let (path, query) = parse_url(&requests[1]);
+    assert_eq!(path, "/teams");
+    assert_eq!(query["filter[id]"], "5");
+}
+
+#[test]
+fn read_ahead_fetches_planned_pages_concurrently() {
+    let transport = MockTransport::default()
+        .with_body(
+            "/leagues",
+            &format!("[{}, {}]", league_json(1), league_json(2)),
+        )
+        .with_header("/leagues", "X-Total", "5")
+        .with_header(
+            "/leagues",
+            "Link",
+            r#"<https://api.pandascore.co/leagues?page=2&per_page=2>; rel="next""#,
+        )
+        .with_body(
+            "/leagues?page=2",
+            &format!("[{}, {}]", league_json(3), league_json(4)),
+        )
+        .with_body("/leagues?page=3", &format!("[{}]", league_json(5)));
+    let adapter = Arc::new(
+        Adapter::builder(Client::new(transport.clone(), "token").unwrap())
+            .read_ahead(2)
+            .build(),
+    );
+    let query = r#"{ Leagues(page_size: 2) { id @output } }"#;
+    let mut rows = execute_query(
+        Adapter::<MockTransport>::schema(),
+        adapter,
+        query,
+        BTreeMap::<&str, FieldValue>::new(),
+    )
+    .unwrap();
+
+    // The planned pages are requested as soon as the first one is handed out.
+    let first = rows.next().unwrap();
+    assert_eq!(transport.requests().len(), 3);
+    let ids: Vec<_> = std::iter::once(first)
+        .chain(rows)
+        .map(|row| row["id"].clone())
+        .collect();
+
+    // Later pages carry no `Link` header, so they are only reached through the planned pages.
+    assert_eq!(ids, (1..=5).map(FieldValue::Uint64).collect::<Vec<_>>());
+    let pages: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|url| parse_url(url).1.get("page").cloned())
+        .collect();
+    assert_eq!(pages, [None, Some("2".to_owned()), Some("3".to_owned())]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn adapter_runs_inside_an_existing_runtime() {
+    let transport = MockTransport::default().with_body("/teams/5", TEAM);
+    let adapter = Arc::new(Adapter::with_handle(
+        Client::new(transport.clone(), "token").unwrap(),
+        tokio::runtime::Handle::current(),
+    ));
+    let query = r#"{ Teams { id @filter(op: "=", value: ["$id"]) @output } }"#;
+    let args = BTreeMap::from([("id", FieldValue::Int64(5))]);
+    let rows = execute_query(Adapter::<MockTransport>::schema(), adapter, query, args)
+        .unwrap()
+        .count();
+
+    assert_eq!(rows, 1);
+    assert_eq!(transport.requests(), ["https://api.pandascore.co/teams/5"]);
+}
+
+#[tokio::test]
+async fn adapter_reports_blocking_a_current_thread_runtime() {
+    let transport = MockTransport::default().with_body("/teams/5", TEAM);
+    let adapter = Adapter::with_handle(
+        Client::new(transport.clone(), "token").unwrap(),
+        tokio::runtime::Handle::current(),
+    );
+    let query = r#"{ Teams { id @filter(op: "=", value: ["$id"]) @output } }"#;
+    let args = BTreeMap::from([("id", FieldValue::Int64(5))]);
+    let mut query_rows = adapter.execute_query(query, args).unwrap();
+
+    assert_eq!(query_rows.by_ref().count(), 0);
+    assert!(matches!(
+        query_rows.take_errors().as_slice(),
+        [AdapterError::CurrentThreadRuntime]
+    ));
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn async_adapter_streams_rows_and_stops_when_dropped() {
+    use std::time::Duration;
+
+    use futures_util::StreamExt;
+
+    use crate::{AsyncAdapter, RateLimit, RateLimiter};
+
+    const PAGES: u32 = 200;
+    let mut transport = MockTransport::default()
+        .with_body("/leagues", &format!("[{}]", league_json(1)))
+        .with_header(
+            "/leagues",
+            "Link",
+            r#"<https://api.pandascore.co/leagues?page=2>; rel="next""#,
+        );
+    for page in 2..=PAGES {
+        let path = format!("/leagues?page={page}");
+        let next = format!(
+            r#"<https://api.pandascore.co/leagues?page={}>; rel="next""#,
+            page + 1
+        );
+        transport = transport
+            .with_body(&path, &format!("[{}]", league_json(page.into())))
+            .with_header(&path, "Link", &next);
+    }
+    // Spread the requests out, so the query is still running when the stream is dropped.
+    let limiter = RateLimiter::new(RateLimit::new(1, Duration::from_millis(10)));
+    let adapter = AsyncAdapter::new(
+        Adapter::builder(Client::new(transport.clone(), "token").unwrap())
+            .rate_limiter(limiter)
+            .build(),
+    );
+
+    let mut rows = adapter.execute_query(
+        "{ Leagues { id @output name @filter(op: \"=\", value: [\"$name\"]) } }",
+        BTreeMap::from([("name", "nope")]),
+    );
+    // No league matches, so the stream yields nothing while the query pages through leagues.
+    let pending = tokio::time::timeout(Duration::from_millis(50), rows.next()).await;
+    assert!(pending.is_err());
+    drop(rows);
+
+    let settled = || async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        transport.requests().len()
+    };
+    let requests = settled().await;
+    assert_eq!(settled().await, requests);
+    assert!(requests < PAGES as usize);
+
+    let mut rows = adapter.execute_query(
+        "{ Leagues { id @output } }",
+        BTreeMap::<&str, FieldValue>::new(),
+    );
+    let first = rows.next().await.unwrap().unwrap();
+    assert_eq!(first["id"], FieldValue::Uint64(1));
+    drop(rows);
+
+    let mut rows = adapter.execute_query(
+        "{ Nope { id @output } }",
+        BTreeMap::<&str, FieldValue>::new(),
+    );
+    assert!(matches!(
+        rows.next().await,
+        Some(Err(AdapterError::InvalidQuery(_)))
+    ));
+    assert!(rows.next().await.is_none());
+}
+
+#[test]
+fn rate_limiter_delays_requests_beyond_the_limit() {
+    use std::time::{Duration, Instant};
+
+    use crate::{CacheConfig, RateLimit, RateLimiter};
+
+    let query = r#"{ Teams { id @filter(op: "=", value: ["$id"]) @output } }"#;
+    let run_twice = |transport: MockTransport, limit: RateLimit| {
+        let limiter = RateLimiter::new(limit);
+        let client = Client::new(limiter.track_headers(transport), "token").unwrap();
+        let adapter = Arc::new(
+            Adapter::builder(client)
+                .cache(CacheConfig::disabled())
+                .rate_limiter(limiter)
+                .build(),
+        );
+        let start = Instant::now();
+        for _ in 0..2 {
+            let args = BTreeMap::from([("id", FieldValue::Int64(5))]);
+            let schema = Adapter::<RateLimitHeaders<MockTransport>>::schema();
+            execute_query(schema, Arc::clone(&adapter), query, args)
+                .unwrap()
+                .count();
+        }
+        start.elapsed()
+    };
+
+    let transport = MockTransport::default().with_body("/teams/5", TEAM);
+    let limit = RateLimit::new(10, Duration::from_secs(1));
+    assert!(run_twice(transport.clone(), limit) < Duration::from_millis(50));
+    assert!(run_twice(transport.clone(), limit.burst(1)) >= Duration::from_millis(90));
+
+    // The server reporting the quota as used up holds back requests for the rest of the
+    // limit's period, however fast the bucket refills.
+    let transport = transport.with_header("/teams/5", "X-Rate-Limit-Remaining", "0");
+    let limit = RateLimit::new(100, Duration::from_millis(300));
+    assert!(run_twice(transport, limit) >= Duration::from_millis(250));
+}
+
+#[test]
+fn retry_policy_retries_transient_failures() {
+    use std::time::{Duration, Instant};
+
+    use crate::{RetryAfterHeaders, RetryPolicy};
+
+    let query = r#"{ Teams { id @filter(op: "=", value: ["$id"]) @output } }"#;
+    let run_with = |transport: &MockTransport| {
+        let policy = RetryPolicy::new().backoff(Duration::from_millis(1), Duration::from_millis(1));
+        let client = Client::new(policy.track_headers(transport.clone()), "token").unwrap();
+        let adapter = Arc::new(Adapter::builder(client).retry(policy).build());
+        let args = BTreeMap::from([("id", FieldValue::Int64(5))]);
+        let schema = Adapter::<RetryAfterHeaders<MockTransport>>::schema();
+        let rows = execute_query(schema, Arc::clone(&adapter), query, args)
+            .unwrap()
+            .count();
+        let errors = adapter.errors().len();
+        (rows, errors)
+    };
+
+    let transport = MockTransport::default()
+        .with_body("/teams/5", TEAM)
+        .with_failures("/teams/5", &[502, 503]);
+    assert_eq!(run_with(&transport), (1, 0));
+    assert_eq!(transport.requests().len(), 3);
+
+    // Attempts are capped, and statuses that won't change aren't retried.
+    let transport = transport.with_failures("/teams/5", &[502, 502, 502]);
+    assert_eq!(run_with(&transport), (0, 1));
+    assert_eq!(transport.requests().len(), 6);
+    let transport = transport.with_failures("/teams/5", &[404]);
+    assert_eq!(run_with(&transport), (0, 1));
+    assert_eq!(transport.requests().len(), 7);
+
+    // The retry waits as long as the server asks, beyond the backoff.
+    let transport = MockTransport::default()
+        .with_body("/teams/5", TEAM)
+        .with_header("/teams/5", "Retry-After", "1")
+        .with_failures("/teams/5", &[429]);
+    let start = Instant::now();
+    assert_eq!(run_with(&transport), (1, 0));
+    assert!(start.elapsed() >= Duration::from_secs(1));
+
+    // The server can ask for a date rather than a delay.
+    let format = time::format_description::parse_borrowed::<2>(
+        "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT",
+    )
+    .unwrap();
+    let date = (time::OffsetDateTime::now_utc() + Duration::from_secs(2))
+        .format(&format)
+        .unwrap();
+    let transport = MockTransport::default()
+        .with_body("/teams/5", TEAM)
+        .with_header("/teams/5", "Retry-After", &date)
+        .with_failures("/teams/5", &[429]);
+    let start = Instant::now();
+    assert_eq!(run_with(&transport), (1, 0));
+    assert!(start.elapsed() >= Duration::from_secs(1));
+
+    assert_eq!(RetryPolicy::default(), RetryPolicy::none());
+}
+
+#[test]
+fn fail_fast_ends_the_query_with_the_first_error() {
+    use crate::ErrorPolicy;
+
+    let transport = MockTransport::default()
+        .with_body(
+            "/leagues",
+            &format!("[{}, {}]", league_json(1), league_json(2)),
+        )
+        .with_failures("/leagues/1/series", &[500])
+        .with_body("/leagues/2/series", &format!("[{}]", series_json(3, 2)));
+    let query = r#"{ Leagues { series { id @output } } }"#;
+    let run_with = |transport: &MockTransport, policy: ErrorPolicy| {
+        let client = Client::new(transport.clone(), "token").unwrap();
+        let adapter = Adapter::builder(client).error_policy(policy).build();
+        let mut query_rows = adapter
+            .execute_query(query, BTreeMap::<&str, FieldValue>::new())
+            .unwrap();
+        let rows: Vec<_> = query_rows.by_ref().collect();
+        // Errors are kept with the query that raised them.
+        assert!(adapter.errors().is_empty());
+        (rows, query_rows.take_errors())
+    };
+
+    let (rows, errors) = run_with(&transport, ErrorPolicy::Collect);
+    assert!(matches!(rows.as_slice(), [Ok(_)]));
+    let [AdapterError::EndpointError { context, .. }] = errors.as_slice() else {
+        panic!("expected one endpoint error, got {errors:?}");
+    };
+    assert_eq!(
+        context.to_string(),
+        "League.series of vertex 1, /leagues/1/series, page 1"
+    );
+
+    let transport = transport.with_failures("/leagues/1/series", &[500]);
+    let (rows, errors) = run_with(&transport, ErrorPolicy::FailFast);
+    assert!(matches!(
+        rows.as_slice(),
+        [Err(AdapterError::EndpointError { .. })]
+    ));
+    assert!(errors.is_empty());
+    let paths: Vec<_> = transport
+        .requests()
+        .iter()
+        .skip(3)
+        .map(|url| parse_url(url).0)
+        .collect();
+    assert_eq!(paths, ["/leagues", "/leagues/1/series"]);
+}
+
+#[test]
+fn queries_keep_their_own_errors_and_stats() {
+    use std::time::Duration;
+
+    use crate::{CacheConfig, QueryStats, RetryPolicy};
+
+    let transport = MockTransport::default()
+        .with_body("/teams/5", TEAM)
+        .with_failures("/teams/5", &[502]);
+    let client = Client::new(transport.clone(), "token").unwrap();
+    let policy = RetryPolicy::new().backoff(Duration::from_millis(1), Duration::from_millis(1));
+    let adapter = Adapter::builder(client)
+        .cache(CacheConfig::default())
+        .retry(policy)
+        .build();
+    let query = r#"{ Teams { id @filter(op: "=", value: ["$id"]) @output } }"#;
+    let run_with = |adapter: &Adapter<MockTransport>, id: i64| {
+        let args = BTreeMap::from([("id", FieldValue::Int64(id))]);
+        let mut query_rows = adapter.execute_query(query, args).unwrap();
+        let rows = query_rows.by_ref().count();
+        (rows, query_rows.take_errors().len(), query_rows.stats())
+    };
+
+    let (rows, errors, stats) = run_with(&adapter, 5);
+    assert_eq!((rows, errors), (1, 0));
+    assert_eq!(
+        (stats.requests, stats.retries, stats.cache_misses),
+        (2, 1, 1)
+    );
+
+    // The second query is answered from the cache the first one filled.
+    let (rows, errors, stats) = run_with(&adapter, 5);
+    assert_eq!((rows, errors), (1, 0));
+    assert_eq!(
+        stats,
+        QueryStats {
+            cache_hits: 1,
+            ..QueryStats::default()
+        }
+    );
+
+    // Sessions keep the errors of queries run through trustfall apart from the adapter's.
+    let session = Arc::new(adapter.session());
+    let args = BTreeMap::from([("id", FieldValue::Int64(6))]);
+    let rows = execute_query(
+        Adapter::<MockTransport>::schema(),
+        Arc::clone(&session),
+        query,
+        args,
+    )
+    .unwrap()
+    .count();
+    assert_eq!(rows, 0);
+    assert_eq!(session.errors().len(), 1);
+    assert_eq!(session.stats().failed_requests, 1);
+    assert!(adapter.errors().is_empty());
+    assert_eq!(adapter.stats(), QueryStats::default());
+}
+
+#[test]
+fn unknown_schema_fields_are_reported_instead_of_panicking() {
+    use trustfall::Schema;
+
+    let schema_text = Adapter::<MockTransport>::SCHEMA_TEXT.replacen(
+        "type League {\n",
+        "type League {\n    nickname: String\n    owner: Team\n",
+        1,
+    );
+    let schema = Schema::parse(schema_text).unwrap();
+    let transport =
+        MockTransport::default().with_body("/leagues", &format!("[{}]", league_json(1)));
+    let adapter = Arc::new(Adapter::new(
+        Client::new(transport.clone(), "token").unwrap(),
+    ));
+
+    let query = r#"{ Leagues { nickname @output } }"#;
+    let rows: Vec<_> = execute_query(
+        &schema,
+        Arc::clone(&adapter),
+        query,
+        BTreeMap::<&str, FieldValue>::new(),
+    )
+    .unwrap()
+    .collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["nickname"], FieldValue::Null);
+
+    let query = r#"{ Leagues { owner { id @output } } }"#;
+    let rows = execute_query(
+        &schema,
+        Arc::clone(&adapter),
+        query,
+        BTreeMap::<&str, FieldValue>::new(),
+    )
+    .unwrap()
+    .count();
+    assert_eq!(rows, 0);
+
+    let errors: Vec<_> = adapter.errors().iter().map(ToString::to_string).collect();
+    assert_eq!(
+        errors,
+        [
+            "unknown property 'nickname' on type 'League'",
+            "unknown edge 'owner' on type 'League'",
+        ]
+    );
+}
+
+#[test]
+fn fail_fast_records_the_error_of_queries_run_through_trustfall() {
+    use crate::ErrorPolicy;
+
+    let transport = MockTransport::default()
+        .with_body(
+            "/leagues",
+            &format!("[{}, {}]", league_json(1), league_json(2)),
+        )
+        .with_failures("/leagues/1/series", &[500])
+        .with_body("/leagues/2/series", &format!("[{}]", series_json(3, 2)));
+    let client = Client::new(transport.clone(), "token").unwrap();
+    let adapter = Arc::new(
+        Adapter::builder(client)
+            .error_policy(ErrorPolicy::FailFast)
+            .build(),
+    );
+    let query = r#"{ Leagues { id @output series @fold { series_id: id @output } } }"#;
+    let rows = execute_query(
+        Adapter::<MockTransport>::schema(),
+        Arc::clone(&adapter),
+        query,
+        BTreeMap::<&str, FieldValue>::new(),
+    )
+    .unwrap()
+    .count();
+
+    // The query can't be ended early, so league 2 is still yielded without its series.
+    assert_eq!(rows, 2);
+    assert!(matches!(
+        adapter.errors().as_slice(),
+        [AdapterError::EndpointError { .. }]
+    ));
+    let paths: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|url| parse_url(url).0)
+        .collect();
+    assert_eq!(paths, ["/leagues", "/leagues/1/series"]);
+}
+
+#[test]
+fn video_games_list_their_neighbors_by_video_game() {
+    use crate::ErrorPolicy;
+
+    let dota = league_json(2).replace(
+        r#""id": 1, "name": "LoL", "slug": "league-of-legends""#,
+        r#""id": 4, "name": "Dota 2", "slug": "dota-2""#,
+    );
+    let transport = MockTransport::default()
+        .with_body("/lol/leagues", &format!("[{}]", league_json(1)))
+        .with_body("/leagues", &format!("[{dota}]"));
+    let client = Client::new(transport.clone(), "token").unwrap();
+    let adapter = Adapter::builder(client)
+        .error_policy(ErrorPolicy::FailFast)
+        .build();
+    let query = r#"{ VideoGames { slug @output leagues { id @output } } }"#;
+    let mut query_rows = adapter
+        .execute_query(query, BTreeMap::<&str, FieldValue>::new())
+        .unwrap();
+    let rows: Vec<_> = query_rows
+        .by_ref()
+        .map(|row| {
+            let row = row.unwrap();
+            (row["slug"].clone(), row["id"].clone())
+        })
+        .collect();
+
+    // The mock answers every filtered list with the Dota 2 league, which only counts for Dota 2.
+    assert_eq!(
+        rows,
+        [
+            (FieldValue::from("dota-2"), FieldValue::Uint64(2)),
+            (FieldValue::from("league-of-legends"), FieldValue::Uint64(1)),
+        ]
+    );
+    assert!(query_rows.take_errors().is_empty());
+
+    // Games without their own endpoints are listed across all games, filtered by game.
+    let dota_leagues: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|url| parse_url(url))
+        .filter(|(path, query)| {
+            path == "/leagues" && query.get("filter[videogame]").map(String::as_str) == Some("4")
+        })
+        .collect();
+    assert_eq!(dota_leagues.len(), 2);
+    assert!(transport
+        .requests()
+        .iter()
+        .all(|url| !parse_url(url).0.starts_with("/dota2/")));
+}
@@ -1,7 +1,15 @@
 use pandascore::model::{
-    league::League, matches::Match, player::Player, series::Series, team::Team,
-    tournament::Tournament, VideoGame, Winner,
+    league::League,
+    matches::{Match, MatchResult},
+    player::{CompactPlayer, Player},
+    series::Series,
+    team::{CompactTeam, Team},
+    tournament::Tournament,
+    VideoGame, Winner,
 };
+use trustfall::provider::Typename;
+
+use crate::adapter::AdapterError;
 
 #[non_exhaustive]
 #[derive(Debug, Clone, trustfall::provider::TrustfallEnumVertex)]
@@ -14,6 +22,29 @@ pub enum Vertex {
     Player(Player),
     VideoGame(VideoGame),
     Winner(Winner),
-    WinnerTeam { id: u64, team: Team },
-    WinnerPlayer { id: u64, player: Player },
+    WinnerTeam {
+        id: u64,
+        team: Team,
+    },
+    WinnerPlayer {
+        id: u64,
+        player: Player,
+    },
+    OpponentTeam(CompactTeam),
+    OpponentPlayer(CompactPlayer),
+    MatchResult(MatchResult),
+    Roster {
+        team: Option<Team>,
+        players: Vec<CompactPlayer>,
+    },
+}
+
+impl Vertex {
+    /// The error for this vertex turning up where one of type `expected` belongs.
+    pub(super) fn mismatch(&self, expected: &'static str) -> AdapterError {
+        AdapterError::UnexpectedVertex {
+            expected,
+            found: self.typename(),
+        }
+    }
 }
@@ -0,0 +1,130 @@
+use std::{
+    collections::BTreeMap,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures_util::Stream;
+use pandascore::ClientTransport;
+use tokio::sync::mpsc;
+use trustfall::FieldValue;
+
+use crate::{
+    adapter::{QueryState, QueryStats, Row},
+    Adapter, AdapterError,
+};
+
+/// How many rows a query runs ahead of the consumer of its [`RowStream`].
+const BUFFER: usize = 64;
+
+/// Runs queries against an [`Adapter`] from async code.
+///
+/// Each query runs on a thread of its own, since trustfall executes queries as blocking
+/// iterators, and its rows are sent back over a [`RowStream`].
+#[derive(Debug)]
+pub struct AsyncAdapter<T>(Arc<Adapter<T>>);
+
+impl<T> Clone for AsyncAdapter<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> AsyncAdapter<T>
+where
+    T: ClientTransport + Send + Sync + 'static,
+{
+    pub fn new(adapter: Adapter<T>) -> Self {
+        Self(Arc::new(adapter))
+    }
+
+    /// The adapter queries run against.
+    pub fn adapter(&self) -> &Arc<Adapter<T>> {
+        &self.0
+    }
+
+    /// Executes `query` with `args`, streaming its rows as they are produced.
+    ///
+    /// Rows are the same as those of [`Adapter::execute_query`], so an invalid query yields a
+    /// single [`AdapterError::InvalidQuery`].
+    ///
+    /// Dropping the stream cancels the query: no request is sent after that, though requests
+    /// already in flight complete.
+    pub fn execute_query(
+        &self,
+        query: impl Into<String>,
+        args: BTreeMap<impl Into<Arc<str>>, impl Into<FieldValue>>,
+    ) -> RowStream {
+        let query = query.into();
+        let args: BTreeMap<Arc<str>, FieldValue> = args
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        let adapter = Arc::clone(&self.0);
+        let state = Arc::new(QueryState::for_rows());
+        let (tx, rx) = mpsc::channel(BUFFER);
+
+        let worker_state = Arc::clone(&state);
+        std::thread::spawn(move || {
+            let rows = match adapter.execute_query_with(worker_state, &query, args) {
+                Ok(rows) => rows,
+                Err(e) => {
+                    let _ = tx.blocking_send(Err(e));
+                    return;
+                }
+            };
+
+            for row in rows {
+                // The stream was dropped, so stop resolving the query.
+                if tx.blocking_send(row).is_err() {
+                    break;
+                }
+            }
+        });
+
+        RowStream { rows: rx, state }
+    }
+}
+
+impl<T> From<Adapter<T>> for AsyncAdapter<T>
+where
+    T: ClientTransport + Send + Sync + 'static,
+{
+    fn from(adapter: Adapter<T>) -> Self {
+        Self::new(adapter)
+    }
+}
+
+/// The rows of a query executed by an [`AsyncAdapter`].
+#[derive(Debug)]
+pub struct RowStream {
+    rows: mpsc::Receiver<Result<Row, AdapterError>>,
+    state: Arc<QueryState>,
+}
+
+impl RowStream {
+    /// Removes and returns the errors this query recorded so far.
+    pub fn take_errors(&self) -> Vec<AdapterError> {
+        self.state.take_errors()
+    }
+
+    /// The work done for this query so far.
+    pub fn stats(&self) -> QueryStats {
+        self.state.stats()
+    }
+}
+
+impl Drop for RowStream {
+    fn drop(&mut self) {
+        self.state.cancel();
+    }
+}
+
+impl Stream for RowStream {
+    type Item = Result<Row, AdapterError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rows.poll_recv(cx)
+    }
+}
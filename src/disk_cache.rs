@@ -0,0 +1,261 @@
+use std::{
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use reqwest::{Request, Response, ResponseBuilderExt, Url};
+use serde::{Deserialize, Serialize};
+use tower_service::Service;
+
+/// Settings for a [`DiskCache`].
+#[derive(Debug, Clone)]
+pub struct DiskCacheConfig {
+    dir: PathBuf,
+    ttl: Duration,
+    ttls: Vec<(String, Duration)>,
+    offline: bool,
+}
+
+impl DiskCacheConfig {
+    /// Stores responses in `dir`, keeping them for an hour by default.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl: Duration::from_secs(60 * 60),
+            ttls: Vec::new(),
+            offline: false,
+        }
+    }
+
+    /// Sets how long responses are kept when no [`ttl_for`](Self::ttl_for) rule matches them.
+    #[must_use]
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets how long responses are kept for endpoints whose path starts with `prefix`, such as
+    /// `/matches/running`.
+    ///
+    /// The longest matching prefix wins.
+    /// Game-specific endpoints start with the game, such as `/lol/matches`.
+    #[must_use]
+    pub fn ttl_for(mut self, prefix: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.push((prefix.into(), ttl));
+        self
+    }
+
+    /// Answers every request from the cache regardless of age, and never touches the network.
+    ///
+    /// Requests missing from the cache fail with `504 Gateway Timeout`.
+    #[must_use]
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    fn ttl_of(&self, path: &str) -> Duration {
+        self.ttls
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.ttl, |(_, ttl)| *ttl)
+    }
+}
+
+/// A transport that stores API responses on disk, keyed by request method and URL.
+///
+/// Query parameters are compared regardless of their order.
+///
+/// Wrap the transport given to [`pandascore::Client::new`] with it to reuse responses across
+/// runs:
+///
+/// ```no_run
+/// # use trustfall_pandascore_adapter::{DiskCache, DiskCacheConfig};
+/// let transport = DiskCache::new(reqwest::Client::new(), DiskCacheConfig::new(".cache"));
+/// let client = pandascore::Client::new(transport, "token").unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiskCache<T> {
+    inner: T,
+    config: Arc<DiskCacheConfig>,
+}
+
+impl<T> DiskCache<T> {
+    pub fn new(inner: T, config: DiskCacheConfig) -> Self {
+        Self {
+            inner,
+            config: Arc::new(config),
+        }
+    }
+}
+
+impl<T> Service<Request> for DiskCache<T>
+where
+    T: Service<Request, Response = Response, Error = reqwest::Error>,
+    T::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = reqwest::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, reqwest::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let method = req.method().to_string();
+        let url = normalize(req.url());
+        let path = self
+            .config
+            .dir
+            .join(format!("{:016x}.json", fnv1a(&method, &url)));
+
+        let ttl = self.config.ttl_of(req.url().path());
+        let cached = Entry::read(&path)
+            .filter(|entry| entry.method == method && entry.url == url)
+            .filter(|entry| self.config.offline || entry.age() < ttl);
+        if let Some(entry) = cached {
+            return Box::pin(std::future::ready(Ok(
+                entry.into_response(req.url().clone())
+            )));
+        }
+        if self.config.offline {
+            let response = build_response(
+                req.url().clone(),
+                StatusCode::GATEWAY_TIMEOUT,
+                HeaderMap::new(),
+                "",
+            );
+            return Box::pin(std::future::ready(Ok(response)));
+        }
+
+        let request_url = req.url().clone();
+        let response = self.inner.call(req);
+        Box::pin(async move {
+            let response = response.await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?;
+
+            if status.is_success() {
+                if let Ok(text) = std::str::from_utf8(&body) {
+                    let entry = Entry {
+                        method,
+                        url,
+                        stored_at: now(),
+                        status: status.as_u16(),
+                        headers: headers
+                            .iter()
+                            .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_owned())))
+                            .collect(),
+                        body: text.to_owned(),
+                    };
+                    entry.write(&path);
+                }
+            }
+
+            Ok(build_response(request_url, status, headers, body))
+        })
+    }
+}
+
+/// A response as stored on disk.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    method: String,
+    url: String,
+    /// Seconds since the Unix epoch.
+    stored_at: u64,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Entry {
+    fn read(path: &Path) -> Option<Self> {
+        let file = fs::read(path).ok()?;
+        serde_json::from_slice(&file).ok()
+    }
+
+    fn write(&self, path: &Path) {
+        let res = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(path, serde_json::to_vec(self)?));
+        #[cfg(feature = "log")]
+        if let Err(e) = res {
+            log::warn!("Failed to write disk cache entry {}: {e}", path.display());
+        }
+        #[cfg(not(feature = "log"))]
+        let _ = res;
+    }
+
+    fn age(&self) -> Duration {
+        Duration::from_secs(now().saturating_sub(self.stored_at))
+    }
+
+    fn into_response(self, url: Url) -> Response {
+        let mut headers = HeaderMap::new();
+        for (k, v) in self.headers {
+            if let (Ok(k), Ok(v)) = (HeaderName::try_from(k), HeaderValue::try_from(v)) {
+                headers.append(k, v);
+            }
+        }
+
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        build_response(url, status, headers, self.body)
+    }
+}
+
+/// Builds a response to a request for `url`, keeping the URL for error messages.
+fn build_response(
+    url: Url,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: impl Into<reqwest::Body>,
+) -> Response {
+    let mut response = http::Response::builder()
+        .status(status)
+        .url(url)
+        .body(body.into())
+        .unwrap();
+    *response.headers_mut() = headers;
+    response.into()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Renders `url` with its query pairs sorted, so that the same request is stored once however
+/// its parameters were ordered.
+fn normalize(url: &Url) -> String {
+    let mut pairs: Vec<_> = url.query_pairs().into_owned().collect();
+    if pairs.is_empty() {
+        return url.to_string();
+    }
+    pairs.sort();
+
+    let mut url = url.clone();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+    url.to_string()
+}
+
+/// 64-bit FNV-1a, used for file names since it is stable across Rust versions.
+fn fnv1a(method: &str, url: &str) -> u64 {
+    [method.as_bytes(), b" ", url.as_bytes()]
+        .into_iter()
+        .flatten()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
@@ -1,5 +1,16 @@
 mod adapter;
+mod async_adapter;
+#[cfg(feature = "disk-cache")]
+mod disk_cache;
 mod field_value;
 mod pagination;
+mod rate_limit;
 
-pub use adapter::{Adapter, AdapterError, Vertex};
+pub use adapter::{
+    Adapter, AdapterBuilder, AdapterError, CacheConfig, ErrorContext, ErrorPolicy, QueryRows,
+    QueryStats, RetryAfterHeaders, RetryPolicy, Row, Vertex,
+};
+pub use async_adapter::{AsyncAdapter, RowStream};
+#[cfg(feature = "disk-cache")]
+pub use disk_cache::{DiskCache, DiskCacheConfig};
+pub use rate_limit::{RateLimit, RateLimitHeaders, RateLimiter};
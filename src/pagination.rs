@@ -1,50 +1,192 @@
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    collections::VecDeque, future::Future, ops::RangeInclusive, pin::Pin, sync::Arc, task::Poll,
+};
 
 use pandascore::{
-    endpoint::{ListResponse, PaginatedEndpoint},
+    endpoint::{CollectionOptions, ListResponse, PaginatedEndpoint},
     ClientTransport,
 };
 
-use crate::adapter::AdapterInner;
+use crate::adapter::{cache::Cached, AdapterInner, ErrorContext};
 
-#[derive(Debug)]
-pub struct PaginationIterator<C, E: PaginatedEndpoint> {
+pub struct PaginationIterator<'a, C, E: PaginatedEndpoint> {
     adapter: Arc<AdapterInner<C>>,
     init: Option<E>,
+    /// The pages left to fetch, once the page count is known from the first response.
+    planned: Option<Plan<E>>,
+    /// The planned pages being fetched ahead of the rows being consumed.
+    batch: Option<Batch<'a, E::Response>>,
     results: VecDeque<E::Item>,
+    /// Where requests are made from, for attributing errors.
+    context: ErrorContext,
+    /// The number of the page `init` requests.
+    page: u32,
+}
+
+/// A batch of planned pages, fetched while the pages before it are consumed.
+enum Batch<'a, R> {
+    /// Requests that progress whenever a row is handed out.
+    Pending(Pin<Box<dyn Future<Output = Vec<Option<R>>> + 'a>>),
+    /// Responses waiting for the pages before them to be consumed.
+    Ready(Vec<Option<R>>),
+}
+
+/// The remaining pages of a collection whose size was given by the `X-Total` header.
+#[derive(Debug)]
+struct Plan<E> {
+    req: E,
+    options: CollectionOptions,
+    pages: RangeInclusive<u32>,
 }
 
-impl<C, E> PaginationIterator<C, E>
+impl<C, E> PaginationIterator<'_, C, E>
 where
     C: ClientTransport,
     E: PaginatedEndpoint,
 {
-    pub fn new(adapter: Arc<AdapterInner<C>>, init: E) -> Self {
+    pub fn new(adapter: Arc<AdapterInner<C>>, init: E, context: ErrorContext) -> Self {
         Self {
             adapter,
             init: Some(init),
+            planned: None,
+            batch: None,
             results: VecDeque::new(),
+            context,
+            page: 1,
         }
     }
 }
 
-impl<C, E, T> Iterator for PaginationIterator<C, E>
+impl<'a, C, E, T> PaginationIterator<'a, C, E>
 where
-    C: ClientTransport,
-    E: PaginatedEndpoint<Item = T, Response = ListResponse<T>> + Clone + std::fmt::Debug,
+    C: ClientTransport + 'a,
+    E: PaginatedEndpoint<Item = T, Response = ListResponse<T>> + Clone + std::fmt::Debug + 'a,
+    T: Cached + 'a,
+{
+    /// Plans the pages after the first one, so that they can be fetched in concurrent batches.
+    ///
+    /// Returns `None` when read-ahead is disabled, or the response doesn't tell how many pages
+    /// are left.
+    fn plan(&self, req: &E, response: &ListResponse<T>) -> Option<Plan<E>> {
+        let options = response.next.clone()?;
+        let page_size = response.results.len() as u64;
+        // Only plan from the first page, which the `next` link points past.
+        if self.adapter.read_ahead() == 0
+            || page_size == 0
+            || response.total <= page_size
+            || options.clone().page(2) != options
+        {
+            return None;
+        }
+
+        let last_page = u32::try_from(response.total.div_ceil(page_size)).ok()?;
+        Some(Plan {
+            req: req.clone(),
+            options,
+            pages: 2..=last_page,
+        })
+    }
+
+    /// Starts fetching the next planned page, along with up to `read_ahead` pages after it, in
+    /// one batch.
+    fn start_batch(&mut self) {
+        let Some(plan) = &mut self.planned else {
+            return;
+        };
+        let requests = plan
+            .pages
+            .by_ref()
+            .take(1 + self.adapter.read_ahead())
+            .map(|page| {
+                let req = plan
+                    .req
+                    .clone()
+                    .with_options(plan.options.clone().page(page));
+                (req, self.context.clone().page(page))
+            })
+            .collect::<Vec<_>>();
+        if requests.is_empty() {
+            self.planned = None;
+            return;
+        }
+
+        let adapter = Arc::clone(&self.adapter);
+        self.batch = Some(Batch::Pending(Box::pin(async move {
+            adapter.send_all(requests).await
+        })));
+        self.advance_batch();
+    }
+
+    /// Lets the pending batch progress without waiting for it.
+    fn advance_batch(&mut self) {
+        if let Some(Batch::Pending(future)) = &mut self.batch {
+            if let Poll::Ready(responses) = self.adapter.poll(future) {
+                self.batch = Some(Batch::Ready(responses));
+            }
+        }
+    }
+
+    /// Hands out the pages of the batch, waiting for them if needed, and starts the next one.
+    fn take_batch(&mut self) {
+        let responses = match self.batch.take() {
+            Some(Batch::Pending(future)) => match self.adapter.block_on(future) {
+                Some(responses) => responses,
+                None => {
+                    self.planned = None;
+                    return;
+                }
+            },
+            Some(Batch::Ready(responses)) => responses,
+            None => return,
+        };
+
+        for response in responses {
+            // A failed or empty page means the collection changed or ended; stop there.
+            let Some(response) = response.filter(|r| !r.results.is_empty()) else {
+                self.planned = None;
+                return;
+            };
+            self.adapter.remember(&response.results);
+            self.results.extend(response.results);
+        }
+        self.start_batch();
+    }
+}
+
+impl<'a, C, E, T> Iterator for PaginationIterator<'a, C, E>
+where
+    C: ClientTransport + 'a,
+    E: PaginatedEndpoint<Item = T, Response = ListResponse<T>> + Clone + std::fmt::Debug + 'a,
+    T: Cached + 'a,
 {
     type Item = E::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(next) = self.results.pop_front() {
+            self.advance_batch();
             return Some(next);
         }
 
-        if let Some(req) = self.init.take() {
-            let response = self.adapter.execute(req.clone())?;
+        if self.batch.is_some() {
+            self.take_batch();
+            self.results.pop_front()
+        } else if let Some(req) = self.init.take() {
+            let context = self.context.clone().page(self.page);
+            let response = self.adapter.execute(req.clone(), context)?;
 
-            self.init = response.next.map(|opts| req.with_options(opts));
+            self.planned = self.plan(&req, &response);
+            if self.planned.is_none() {
+                // A `next` link that lost its page number would fetch the same page forever.
+                let next_page = self.page + 1;
+                self.init = response
+                    .next
+                    .filter(|opts| opts.clone().page(next_page) == *opts)
+                    .map(|opts| req.with_options(opts));
+                self.page = next_page;
+            }
+            self.adapter.remember(&response.results);
             self.results = response.results.into();
+            self.start_batch();
             self.results.pop_front()
         } else {
             None
@@ -0,0 +1,235 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use reqwest::{header::RETRY_AFTER, Request, Response, StatusCode};
+use tower_service::Service;
+
+use crate::adapter::parse_retry_after;
+
+/// The response header PandaScore reports the requests left in the current hour with.
+const REMAINING_HEADER: &str = "X-Rate-Limit-Remaining";
+
+/// How many requests a [`RateLimiter`] allows over time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Requests regained per second.
+    rate: f64,
+    /// The most requests that can be made at once.
+    burst: u32,
+    /// The window the requests are counted in.
+    period: Duration,
+}
+
+impl RateLimit {
+    /// Allows `requests` requests every `period`, all of which can be made at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests` or `period` is zero.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        assert!(
+            requests > 0 && !period.is_zero(),
+            "rate limit must allow requests"
+        );
+        Self {
+            rate: f64::from(requests) / period.as_secs_f64(),
+            burst: requests,
+            period,
+        }
+    }
+
+    /// Allows `requests` requests an hour, matching a PandaScore plan's hourly quota.
+    ///
+    /// The free plan allows 1,000 requests an hour.
+    pub fn per_hour(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60 * 60))
+    }
+
+    /// Sets the most requests that can be made at once, spreading the rest over time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    #[must_use]
+    pub fn burst(mut self, burst: u32) -> Self {
+        assert!(burst > 0, "burst must allow a request");
+        self.burst = burst;
+        self
+    }
+}
+
+/// A token bucket that delays requests to stay within a [`RateLimit`].
+///
+/// Clones share their bucket, so one limiter can be given to the adapter and, with
+/// [`track_headers`](Self::track_headers), to its transport.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Requests that can be made now. Negative when requests are waiting for their turn.
+    tokens: f64,
+    updated: Instant,
+    /// When the server allows requests again, after it asked to back off with `Retry-After`.
+    paused_until: Option<Instant>,
+    /// The requests the server last reported left, which bound the bucket until its window
+    /// passes.
+    quota: Option<Quota>,
+}
+
+#[derive(Debug)]
+struct Quota {
+    remaining: u64,
+    until: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: f64::from(limit.burst),
+                updated: Instant::now(),
+                paused_until: None,
+                quota: None,
+            })),
+        }
+    }
+
+    /// Wraps `transport` to lower the limiter's budget to the `X-Rate-Limit-Remaining` header
+    /// of every response, so that requests made elsewhere with the same token are accounted for.
+    ///
+    /// The server doesn't say when its count resets, so the reported budget holds for the
+    /// limit's period from when it was first seen, however fast the bucket refills.
+    ///
+    /// The `Retry-After` header of `429 Too Many Requests` responses holds back every request
+    /// until the server allows them again.
+    pub fn track_headers<T>(&self, transport: T) -> RateLimitHeaders<T> {
+        RateLimitHeaders {
+            inner: transport,
+            limiter: self.clone(),
+        }
+    }
+
+    /// Waits until a request can be made.
+    pub(crate) async fn acquire(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            #[cfg(feature = "log")]
+            {
+                log::debug!("Rate limited, waiting {wait:?}");
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Takes a token, returning how long to wait until it is available.
+    fn reserve(&self) -> Duration {
+        let mut bucket = self.refill();
+        bucket.tokens -= 1.0;
+        let wait = if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.limit.rate)
+        };
+        let now = Instant::now();
+        let paused = bucket
+            .paused_until
+            .map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+        let exhausted = match &mut bucket.quota {
+            Some(quota) if quota.until > now => match quota.remaining.checked_sub(1) {
+                Some(remaining) => {
+                    quota.remaining = remaining;
+                    Duration::ZERO
+                }
+                None => quota.until - now,
+            },
+            _ => Duration::ZERO,
+        };
+        wait.max(paused).max(exhausted)
+    }
+
+    fn pause(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.paused_until = bucket.paused_until.max(Some(until));
+    }
+
+    fn observe_remaining(&self, remaining: u64) {
+        let mut bucket = self.refill();
+        bucket.tokens = bucket.tokens.min(remaining as f64);
+        let now = Instant::now();
+        let until = match &bucket.quota {
+            Some(quota) if quota.until > now => quota.until,
+            _ => now + self.limit.period,
+        };
+        bucket.quota = Some(Quota { remaining, until });
+    }
+
+    fn refill(&self) -> MutexGuard<'_, Bucket> {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let regained = now.duration_since(bucket.updated).as_secs_f64() * self.limit.rate;
+        bucket.tokens = (bucket.tokens + regained).min(f64::from(self.limit.burst));
+        bucket.updated = now;
+        bucket
+    }
+}
+
+/// A transport that feeds PandaScore's rate limit headers into a [`RateLimiter`].
+///
+/// Created with [`RateLimiter::track_headers`].
+#[derive(Debug, Clone)]
+pub struct RateLimitHeaders<T> {
+    inner: T,
+    limiter: RateLimiter,
+}
+
+impl<T> Service<Request> for RateLimitHeaders<T>
+where
+    T: Service<Request, Response = Response, Error = reqwest::Error>,
+    T::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = reqwest::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, reqwest::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let response = self.inner.call(req);
+        let limiter = self.limiter.clone();
+        Box::pin(async move {
+            let response = response.await?;
+            let remaining = response
+                .headers()
+                .get(REMAINING_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok());
+            if let Some(remaining) = remaining {
+                limiter.observe_remaining(remaining);
+            }
+
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                let retry_after = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(parse_retry_after);
+                if let Some(wait) = retry_after {
+                    limiter.pause(wait);
+                }
+            }
+            Ok(response)
+        })
+    }
+}